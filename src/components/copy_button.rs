@@ -0,0 +1,57 @@
+use crate::{services::clipboard, utils::ResultExt};
+use yew::prelude::*;
+
+#[derive(Clone, Eq, PartialEq, Properties)]
+pub struct Props {
+    pub text: String,
+}
+
+pub enum Msg {
+    Copy,
+}
+
+pub struct CopyButton {
+    props: Props,
+    link: ComponentLink<Self>,
+    copied: bool,
+}
+impl Component for CopyButton {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            link,
+            copied: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Copy => {
+                self.copied = clipboard::write_text(&self.props.text)
+                    .ok_or_log("failed to copy to clipboard")
+                    .is_some();
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props == props {
+            false
+        } else {
+            self.props = props;
+            self.copied = false;
+            true
+        }
+    }
+
+    fn view(&self) -> Html {
+        let label = if self.copied { "copied" } else { "copy" };
+        html! {
+            <button onclick=self.link.callback(|_| Msg::Copy)>{ label }</button>
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::services::diagnostics::{self, Diagnostic, Level};
+use yew::prelude::*;
+
+fn render_diagnostic(diagnostic: &Diagnostic) -> Html {
+    let level = diagnostic.level.as_str();
+    html! {
+        <li class=level>
+            <em>{ level }</em>{ ": " }{ &diagnostic.message }
+        </li>
+    }
+}
+
+pub enum Msg {
+    Changed,
+    Toggle,
+    Clear,
+}
+
+pub struct DiagnosticsPanel {
+    link: ComponentLink<Self>,
+    diagnostics: Vec<Diagnostic>,
+    expanded: bool,
+    _subscription: diagnostics::Subscription,
+}
+impl DiagnosticsPanel {
+    fn count(&self, level: Level) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == level)
+            .count()
+    }
+}
+impl Component for DiagnosticsPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let subscription = diagnostics::subscribe(link.callback(|()| Msg::Changed));
+        Self {
+            link,
+            diagnostics: diagnostics::get_all(),
+            expanded: false,
+            _subscription: subscription,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Changed => {
+                self.diagnostics = diagnostics::get_all();
+                true
+            }
+            Msg::Toggle => {
+                self.expanded = !self.expanded;
+                true
+            }
+            Msg::Clear => {
+                // the subscription takes care of updating the list
+                diagnostics::clear();
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        if self.diagnostics.is_empty() {
+            return html! {};
+        }
+
+        let summary = format!(
+            "{} warning(s), {} error(s)",
+            self.count(Level::Warning),
+            self.count(Level::Error)
+        );
+        let list = if self.expanded {
+            html! {
+                <ul>
+                    { for self.diagnostics.iter().map(render_diagnostic) }
+                </ul>
+            }
+        } else {
+            html! {}
+        };
+
+        html! {
+            <aside class="diagnostics">
+                <button onclick=self.link.callback(|_| Msg::Toggle)>{ summary }</button>
+                <button onclick=self.link.callback(|_| Msg::Clear)>{ "clear" }</button>
+                { list }
+            </aside>
+        }
+    }
+}
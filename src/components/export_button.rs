@@ -0,0 +1,48 @@
+use crate::{
+    services::{diagnostics, download, results},
+    utils::ResultExt,
+};
+use yew::prelude::*;
+
+const FILE_NAME: &str = "aoc2020-results.json";
+
+pub enum Msg {
+    Export,
+}
+
+pub struct ExportButton {
+    link: ComponentLink<Self>,
+}
+impl Component for ExportButton {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Export => {
+                let export = results::export();
+                if export.days.is_empty() {
+                    diagnostics::warn("nothing to export yet, solve a puzzle first");
+                } else {
+                    download::download_text(FILE_NAME, "application/json", &export.to_json())
+                        .ok_or_log("failed to download results");
+                }
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <button onclick=self.link.callback(|_| Msg::Export)>{ "export results" }</button>
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::days::{self, Example, PartCheck};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+fn render_check(check: &PartCheck) -> Html {
+    let text = if check.passed() {
+        format!("part {}: ok", check.part)
+    } else {
+        format!(
+            "part {}: expected {}, got {}",
+            check.part,
+            check.expected,
+            check.actual.as_deref().unwrap_or("nothing")
+        )
+    };
+    let class = if check.passed() { "passed" } else { "failed" };
+
+    html! {
+        <li class=class>{ text }</li>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub day: usize,
+    /// The text area to fill with the example input.
+    pub input: NodeRef,
+    /// Called after the example was loaded into the text area.
+    #[prop_or_default]
+    pub onload: Callback<()>,
+}
+
+pub enum Msg {
+    Load(usize),
+}
+
+/// Buttons to load the official examples of a day.
+///
+/// Loading an example also checks the solver's answers against the expected ones.
+pub struct LoadExample {
+    props: Props,
+    link: ComponentLink<Self>,
+    checks: Vec<PartCheck>,
+}
+impl LoadExample {
+    fn examples(&self) -> &'static [Example] {
+        days::get_puzzle(self.props.day).map_or(&[], |puzzle| puzzle.examples)
+    }
+}
+impl Component for LoadExample {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            link,
+            checks: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Load(index) => {
+                let puzzle = match days::get_puzzle(self.props.day) {
+                    Some(v) => v,
+                    None => return false,
+                };
+                let (example, input_area) = match (
+                    puzzle.examples.get(index),
+                    self.props.input.cast::<HtmlTextAreaElement>(),
+                ) {
+                    (Some(example), Some(input_area)) => (example, input_area),
+                    _ => return false,
+                };
+
+                input_area.set_value(&example.input());
+                self.checks = example.check(puzzle.parts);
+                self.props.onload.emit(());
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props == props {
+            false
+        } else {
+            self.props = props;
+            self.checks.clear();
+            true
+        }
+    }
+
+    fn view(&self) -> Html {
+        let examples = self.examples();
+        let buttons = (0..examples.len()).map(|i| {
+            let label = if examples.len() == 1 {
+                "load example".to_owned()
+            } else {
+                format!("load example {}", i + 1)
+            };
+            html! {
+                <button onclick=self.link.callback(move |_| Msg::Load(i))>{ label }</button>
+            }
+        });
+
+        html! {
+            <div class="examples">
+                { for buttons }
+                <ul>
+                    { for self.checks.iter().map(render_check) }
+                </ul>
+            </div>
+        }
+    }
+}
@@ -1 +1,6 @@
 pub mod calendar;
+pub mod copy_button;
+pub mod diagnostics;
+pub mod export_button;
+pub mod load_example;
+pub mod share_button;
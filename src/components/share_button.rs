@@ -0,0 +1,93 @@
+use crate::{
+    services::{
+        clipboard, diagnostics,
+        routing::{self, Route},
+        share::{self, Link},
+    },
+    utils::ResultExt,
+};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub day: usize,
+    /// The text area containing the input to share.
+    pub input: NodeRef,
+}
+
+pub enum Msg {
+    Share,
+}
+
+pub struct ShareButton {
+    props: Props,
+    link: ComponentLink<Self>,
+    shared: Option<Link>,
+}
+impl Component for ShareButton {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            link,
+            shared: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Share => {
+                let input = match self.props.input.cast::<HtmlTextAreaElement>() {
+                    Some(v) => v.value(),
+                    None => return false,
+                };
+
+                let link = share::create_link(Route::Day(self.props.day), &input);
+                match &link {
+                    Link::WithInput(url) => routing::replace_current_url(url),
+                    Link::WithoutInput(_) => diagnostics::warn(
+                        "the input is too large to be embedded in a link, share it separately",
+                    ),
+                }
+                clipboard::write_text(link.url()).ok_or_log("failed to copy link to clipboard");
+
+                self.shared = Some(link);
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props == props {
+            false
+        } else {
+            self.props = props;
+            self.shared = None;
+            true
+        }
+    }
+
+    fn view(&self) -> Html {
+        let shared = match &self.shared {
+            Some(Link::WithInput(url)) => html! {
+                <span class="aside">{ " copied " }<a href=url.clone()>{ "link" }</a></span>
+            },
+            Some(Link::WithoutInput(url)) => html! {
+                <span class="aside">
+                    { " input too large, copied " }<a href=url.clone()>{ "link" }</a>{ " without it" }
+                </span>
+            },
+            None => html! {},
+        };
+
+        html! {
+            <>
+                <button onclick=self.link.callback(|_| Msg::Share)>{ "share" }</button>
+                { shared }
+            </>
+        }
+    }
+}
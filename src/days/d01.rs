@@ -1,77 +1,348 @@
-use std::{collections::HashSet, num::ParseIntError};
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
 use web_sys::HtmlTextAreaElement;
 use yew::prelude::*;
 
+/// Wide enough to sum up any number of entries the page allows.
+type Sum = i128;
+
+/// Integer types an expense report can consist of.
+trait Entry: Copy + Ord + Display + FromStr + Into<Sum> {}
+impl<T> Entry for T where T: Copy + Ord + Display + FromStr + Into<Sum> {}
+
 #[derive(Eq, PartialEq)]
-struct Solution {
-    entries: Vec<u16>,
-    result: u32,
+struct Solution<T> {
+    entries: Vec<T>,
+    /// Product of the entries, `None` if it overflows.
+    result: Option<Sum>,
 }
-impl Solution {
-    fn from_entries(entries: Vec<u16>) -> Self {
-        let result = entries.iter().copied().map(u32::from).product();
+impl<T: Entry> Solution<T> {
+    fn from_entries(entries: Vec<T>) -> Self {
+        let result = entries
+            .iter()
+            .try_fold(1, |product: Sum, &entry| product.checked_mul(entry.into()));
         Self { entries, result }
     }
 
-    fn solve_n(report: &HashSet<u16>, target: u16, n: u8) -> Option<Self> {
-        fn inner(report: &HashSet<u16>, target: u16, entries: &mut [u16], sum: u16) -> bool {
-            match entries.len() {
-                0 => false,
-                1 => {
-                    let missing = match target.checked_sub(sum) {
-                        Some(v) => v,
-                        None => return false,
-                    };
-                    entries[0] = missing;
-                    report.contains(&missing)
-                }
-                _ => {
-                    for &entry in report {
-                        if inner(report, target, &mut entries[1..], sum + entry) {
-                            entries[0] = entry;
-                            return true;
-                        }
-                    }
-                    false
-                }
+    /// Find `n` entries of the report which sum up to `target`.
+    ///
+    /// The report must be sorted in ascending order.
+    /// Every entry is used at most once unless `allow_reuse` is set.
+    fn solve_n(report: &[T], target: T, n: u8, allow_reuse: bool) -> Option<Self> {
+        if n == 0 {
+            return None;
+        }
+
+        let mut search = Search::new(report, allow_reuse);
+        if search.find(0, n, target.into()) {
+            Some(Self::from_entries(search.entries))
+        } else {
+            None
+        }
+    }
+
+    /// Find every distinct combination of `n` entries which sum up to `target`.
+    ///
+    /// At most `limit` combinations are collected, but all of them are counted.
+    fn solve_all(report: &[T], target: T, n: u8, allow_reuse: bool, limit: usize) -> Solutions<T> {
+        if n == 0 {
+            return Solutions::default();
+        }
+
+        let mut search = Search::new(report, allow_reuse);
+        let mut combinations = Vec::new();
+        search.visit(0, n, target.into(), &mut |entries| {
+            if combinations.len() < limit {
+                combinations.push(Self::from_entries(entries.to_vec()));
             }
+            combinations.len() < limit
+        });
+        let count = search.count(0, n, target.into());
+        Solutions {
+            combinations,
+            count,
         }
+    }
+}
+
+struct Solutions<T> {
+    /// The first combinations in ascending order.
+    combinations: Vec<Solution<T>>,
+    /// Total number of combinations, which may exceed `combinations.len()`.
+    count: u64,
+}
+impl<T> Default for Solutions<T> {
+    fn default() -> Self {
+        Self {
+            combinations: Vec::new(),
+            count: 0,
+        }
+    }
+}
+impl<T> Solutions<T> {
+    fn is_truncated(&self) -> bool {
+        self.count > self.combinations.len() as u64
+    }
+}
+
+/// Depth first search over a sorted report.
+///
+/// Candidates are pruned using the smallest and largest sum that can still be reached.
+struct Search<'a, T> {
+    report: &'a [T],
+    /// `prefix_sums[i]` is the sum of the first `i` entries.
+    prefix_sums: Vec<Sum>,
+    allow_reuse: bool,
+    /// `(start, remaining, target)` combinations which are known to have no solution.
+    dead_ends: HashSet<(usize, u8, Sum)>,
+    /// Memoized results of [`Search::count`].
+    counts: HashMap<(usize, u8, Sum), u64>,
+    entries: Vec<T>,
+    stopped: bool,
+}
+impl<'a, T: Entry> Search<'a, T> {
+    fn new(report: &'a [T], allow_reuse: bool) -> Self {
+        let prefix_sums = std::iter::once(0)
+            .chain(report.iter().scan(0, |sum, &entry| {
+                *sum += entry.into();
+                Some(*sum)
+            }))
+            .collect();
+        Self {
+            report,
+            prefix_sums,
+            allow_reuse,
+            dead_ends: HashSet::new(),
+            counts: HashMap::new(),
+            entries: Vec::new(),
+            stopped: false,
+        }
+    }
 
-        let mut entries = vec![0; n as usize];
-        if inner(report, target, &mut entries, 0) {
-            Some(Self::from_entries(entries))
+    fn sum_range(&self, start: usize, end: usize) -> Sum {
+        self.prefix_sums[end] - self.prefix_sums[start]
+    }
+
+    /// Smallest and largest sum of `remaining` entries if the entry at `index` is the first one.
+    ///
+    /// Returns `None` if there aren't enough entries left.
+    fn bounds(&self, index: usize, remaining: u8) -> Option<(Sum, Sum)> {
+        let len = self.report.len();
+        let entry: Sum = self.report[index].into();
+        let others = usize::from(remaining - 1);
+        if self.allow_reuse {
+            let largest: Sum = (*self.report.last()?).into();
+            Some((
+                entry * Sum::from(remaining),
+                entry + largest * Sum::from(remaining - 1),
+            ))
+        } else if index + others < len {
+            Some((
+                entry + self.sum_range(index + 1, index + 1 + others),
+                entry + self.sum_range(len - others, len),
+            ))
         } else {
             None
         }
     }
+
+    /// Find the first combination, leaving its entries in `self.entries`.
+    fn find(&mut self, start: usize, remaining: u8, target: Sum) -> bool {
+        let mut found = None;
+        self.visit(start, remaining, target, &mut |entries| {
+            found = Some(entries.to_vec());
+            false
+        });
+        match found {
+            Some(entries) => {
+                self.entries = entries;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Call `f` with the entries of every distinct combination in ascending order.
+    ///
+    /// The search stops as soon as `f` returns `false`.
+    /// Returns whether any combination was found.
+    fn visit(
+        &mut self,
+        start: usize,
+        remaining: u8,
+        target: Sum,
+        f: &mut impl FnMut(&[T]) -> bool,
+    ) -> bool {
+        if remaining == 0 {
+            if target == 0 && !f(&self.entries) {
+                self.stopped = true;
+            }
+            return target == 0;
+        }
+        if self.dead_ends.contains(&(start, remaining, target)) {
+            return false;
+        }
+
+        let mut found = false;
+        for i in start..self.report.len() {
+            // an equal entry has the same outcome as the previous one
+            if i > start && self.report[i - 1] == self.report[i] {
+                continue;
+            }
+
+            let (min_sum, max_sum) = match self.bounds(i, remaining) {
+                Some(v) => v,
+                None => break,
+            };
+            // the entries are sorted so the following ones can only be worse
+            if min_sum > target {
+                break;
+            }
+            if max_sum < target {
+                continue;
+            }
+
+            let entry = self.report[i];
+            self.entries.push(entry);
+            let next = if self.allow_reuse { i } else { i + 1 };
+            found |= self.visit(next, remaining - 1, target - entry.into(), f);
+            self.entries.pop();
+            if self.stopped {
+                return found;
+            }
+        }
+
+        if !found {
+            self.dead_ends.insert((start, remaining, target));
+        }
+        found
+    }
+
+    /// Number of distinct combinations without visiting each of them.
+    fn count(&mut self, start: usize, remaining: u8, target: Sum) -> u64 {
+        if remaining == 0 {
+            return u64::from(target == 0);
+        }
+        if let Some(&count) = self.counts.get(&(start, remaining, target)) {
+            return count;
+        }
+
+        let mut count = 0_u64;
+        for i in start..self.report.len() {
+            if i > start && self.report[i - 1] == self.report[i] {
+                continue;
+            }
+
+            let (min_sum, max_sum) = match self.bounds(i, remaining) {
+                Some(v) => v,
+                None => break,
+            };
+            if min_sum > target {
+                break;
+            }
+            if max_sum < target {
+                continue;
+            }
+
+            let entry = self.report[i];
+            let next = if self.allow_reuse { i } else { i + 1 };
+            count = count.saturating_add(self.count(next, remaining - 1, target - entry.into()));
+        }
+
+        self.counts.insert((start, remaining, target), count);
+        count
+    }
 }
 
-fn parse_input(input: &str) -> Result<HashSet<u16>, ParseIntError> {
-    input
+/// Parse the report and sort it in ascending order.
+fn parse_input<T: Entry>(input: &str) -> Result<Vec<T>, T::Err> {
+    let mut report = input
         .split_whitespace()
         .map(str::parse)
-        .collect::<Result<_, _>>()
+        .collect::<Result<Vec<_>, _>>()?;
+    report.sort_unstable();
+    Ok(report)
 }
 
 pub enum Msg {
     Solve,
     SetTarget(InputData),
     SetN(InputData),
+    ToggleReuse,
 }
 
 pub struct Page {
     link: ComponentLink<Self>,
     input_area: NodeRef,
-    target: u16,
+    target: i64,
     n: u8,
-    solution: Option<Solution>,
+    allow_reuse: bool,
+    solutions: Solutions<i64>,
 }
 impl Page {
-    fn render_solution(&self) -> Html {
-        if let Some(solution) = &self.solution {
-            html! { <code>{ &solution.result }</code> }
+    /// Maximum number of combinations listed on the page.
+    const MAX_LISTED: usize = 100;
+
+    fn render_combination(solution: &Solution<i64>) -> Html {
+        let entries = solution
+            .entries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" + ");
+        html! {
+            <li>
+                <span>{ entries }</span>
+                { Self::render_product(solution.result) }
+            </li>
+        }
+    }
+
+    fn render_product(product: Option<Sum>) -> Html {
+        if let Some(product) = product {
+            html! {
+                <>
+                    <code>{ product }</code>
+                    <CopyButton text=product.to_string() />
+                </>
+            }
         } else {
-            html! {}
+            html! { <span class="overflow">{ "product overflows" }</span> }
+        }
+    }
+
+    fn render_solutions(&self) -> Html {
+        let solutions = &self.solutions;
+        if solutions.count == 0 {
+            return html! {};
+        }
+
+        let summary = if solutions.is_truncated() {
+            format!(
+                "{} combinations, showing the first {}",
+                solutions.count,
+                solutions.combinations.len()
+            )
+        } else if solutions.count == 1 {
+            "1 combination".to_owned()
+        } else {
+            format!("{} combinations", solutions.count)
+        };
+        html! {
+            <>
+                <p>{ summary }</p>
+                <ol class="combinations">
+                    { for solutions.combinations.iter().map(Self::render_combination) }
+                </ol>
+            </>
         }
     }
 }
@@ -85,7 +356,8 @@ impl Component for Page {
             input_area: NodeRef::default(),
             target: 2020,
             n: 2,
-            solution: None,
+            allow_reuse: false,
+            solutions: Solutions::default(),
         }
     }
 
@@ -93,16 +365,24 @@ impl Component for Page {
         match msg {
             Msg::Solve => {
                 let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
-                let input = parse_input(&input_area.value());
+                let raw_input = input_area.value();
+                let input = parse_input::<i64>(&raw_input);
                 if let Ok(input) = input {
-                    self.solution = Solution::solve_n(&input, self.target, self.n);
-                    if self.solution.is_some() {
+                    self.solutions = Solution::solve_all(
+                        &input,
+                        self.target,
+                        self.n,
+                        self.allow_reuse,
+                        Self::MAX_LISTED,
+                    );
+                    if self.solutions.count > 0 {
                         input_area.set_custom_validity("");
+                        results::record(1, &raw_input);
                     } else {
                         input_area.set_custom_validity("failed to solve");
                     }
                 } else {
-                    self.solution = None;
+                    self.solutions = Solutions::default();
                     input_area.set_custom_validity("failed to parse");
                 }
 
@@ -124,6 +404,10 @@ impl Component for Page {
                     false
                 }
             }
+            Msg::ToggleReuse => {
+                self.allow_reuse = !self.allow_reuse;
+                true
+            }
         }
     }
 
@@ -131,52 +415,162 @@ impl Component for Page {
         false
     }
 
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
     fn view(&self) -> Html {
         let Self {
             link,
             input_area,
             target,
             n,
+            allow_reuse,
             ..
         } = &self;
         html! {
             <>
-                <input type="number" min="0" value=target oninput=link.callback(Msg::SetTarget) />
+                <input type="number" value=target oninput=link.callback(Msg::SetTarget) />
                 <input type="number" min="0" max="10" value=n oninput=link.callback(Msg::SetN) />
+                <label>
+                    <input type="checkbox" checked=*allow_reuse onclick=link.callback(|_| Msg::ToggleReuse) />
+                    <span>{ "allow reusing entries" }</span>
+                </label>
                 <textarea ref=input_area.clone() />
                 <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
-                { self.render_solution() }
+                <ShareButton day=1 input=input_area.clone() />
+                <LoadExample day=1 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_solutions() }
             </>
         }
     }
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let sol = Solution::solve_n(&parse_input::<i64>(input).ok()?, 2020, 2, false)?;
+    Some(sol.result?.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let sol = Solution::solve_n(&parse_input::<i64>(input).ok()?, 2020, 3, false)?;
+    Some(sol.result?.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    1721
+    979
+    366
+    299
+    675
+    1456
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("514579"), Some("241861950")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        1721
-        979
-        366
-        299
-        675
-        1456
-    "#;
-
-    fn example_input() -> HashSet<u16> {
+    fn example_input() -> Vec<u16> {
         parse_input(EXAMPLE_INPUT).expect("failed to parse input")
     }
 
     #[test]
     fn first() {
-        let sol = Solution::solve_n(&example_input(), 2020, 2).expect("failed to solve");
-        assert_eq!(sol.result, 514_579);
+        let sol = Solution::solve_n(&example_input(), 2020, 2, false).expect("failed to solve");
+        assert_eq!(sol.result, Some(514_579));
     }
 
     #[test]
     fn second() {
-        let sol = Solution::solve_n(&example_input(), 2020, 3).expect("failed to solve");
-        assert_eq!(sol.result, 241_861_950);
+        let sol = Solution::solve_n(&example_input(), 2020, 3, false).expect("failed to solve");
+        assert_eq!(sol.result, Some(241_861_950));
+    }
+
+    #[test]
+    fn entries_used_once() {
+        assert!(Solution::solve_n(&[5, 1010], 2020, 2, false).is_none());
+        let sol = Solution::solve_n(&[5, 1010, 1010], 2020, 2, false).expect("failed to solve");
+        assert_eq!(sol.entries, vec![1010, 1010]);
+    }
+
+    #[test]
+    fn allow_reuse() {
+        let sol = Solution::solve_n(&[5, 1010], 2020, 2, true).expect("failed to solve");
+        assert_eq!(sol.result, Some(1010 * 1010));
+        let sol = Solution::solve_n(&[20, 1000], 2020, 3, true).expect("failed to solve");
+        assert_eq!(sol.entries, vec![20, 1000, 1000]);
+    }
+
+    #[test]
+    fn large_n() {
+        let report = (1..=300).collect::<Vec<u16>>();
+        let sol = Solution::solve_n(&report, 2020, 10, false).expect("failed to solve");
+        assert_eq!(sol.entries.len(), 10);
+        assert_eq!(sol.entries.iter().sum::<u16>(), 2020);
+        assert!(sol.result.is_some());
+
+        // only even entries can't add up to an odd target
+        let report = (1..=100).map(|i| i * 2).collect::<Vec<_>>();
+        assert!(Solution::solve_n(&report, 2021, 10, false).is_none());
+    }
+
+    #[test]
+    fn all_combinations() {
+        let report = parse_input("5 1 4 5 3 2").expect("failed to parse input");
+        let sols = Solution::solve_all(&report, 10, 3, false, usize::MAX);
+        let entries = sols
+            .combinations
+            .iter()
+            .map(|sol| sol.entries.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![vec![1, 4, 5], vec![2, 3, 5]]);
+        assert_eq!(sols.count, 2);
+        assert!(!sols.is_truncated());
+
+        let sols = Solution::solve_all(&report, 10, 2, true, usize::MAX);
+        assert_eq!(sols.count, 1);
+        assert_eq!(sols.combinations[0].entries, vec![5, 5]);
+    }
+
+    #[test]
+    fn all_combinations_limited() {
+        let report = (1..=300).collect::<Vec<_>>();
+        let sols = Solution::solve_all(&report, 300, 2, false, 10);
+        assert_eq!(sols.combinations.len(), 10);
+        assert_eq!(sols.count, 149);
+        assert!(sols.is_truncated());
+    }
+
+    #[test]
+    fn signed_entries() {
+        let report = parse_input::<i32>("-5 3 -1000 7 3020").expect("failed to parse input");
+        let sol = Solution::solve_n(&report, 2020, 2, false).expect("failed to solve");
+        assert_eq!(sol.entries, vec![-1000, 3020]);
+        assert_eq!(sol.result, Some(-3_020_000));
+
+        let sols = Solution::solve_all(&report, 5, 3, false, usize::MAX);
+        assert_eq!(sols.count, 1);
+        assert_eq!(sols.combinations[0].entries, vec![-5, 3, 7]);
+    }
+
+    #[test]
+    fn wide_entries() {
+        let report =
+            parse_input::<u64>("10000000000 30000000000 5").expect("failed to parse input");
+        let sol = Solution::solve_n(&report, 40_000_000_000, 2, false).expect("failed to solve");
+        assert_eq!(sol.result, Some(300_000_000_000_000_000_000));
+
+        let sol = Solution::solve_n(&[u64::MAX; 3], u64::MAX, 1, true).expect("failed to solve");
+        assert_eq!(sol.result, Some(Sum::from(u64::MAX)));
+        let sol = Solution::from_entries(vec![u64::MAX; 3]);
+        assert_eq!(sol.result, None);
     }
 }
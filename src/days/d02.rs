@@ -1,28 +1,284 @@
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::fmt;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
 
+#[derive(Clone, Copy)]
 struct Policy {
     letter: char,
     min: u16,
     max: u16,
 }
 impl Policy {
-    #[allow(clippy::cast_possible_truncation)]
-    fn valid_count(&self, password: &str) -> bool {
-        let count = password.matches(self.letter).count() as u16;
-        (self.min..=self.max).contains(&count)
+    fn check_count(self, password: &str) -> CountReport {
+        let occurrences = password
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == self.letter)
+            .map(|(i, _)| i + 1)
+            .collect();
+        CountReport {
+            occurrences,
+            min: self.min,
+            max: self.max,
+        }
     }
 
-    fn valid_index(&self, password: &str) -> bool {
-        let is_eq = |l| l == self.letter;
-        password
-            .chars()
-            .nth(self.min as usize - 1)
-            .map_or(false, is_eq)
-            ^ password
-                .chars()
-                .nth(self.max as usize - 1)
-                .map_or(false, is_eq)
+    fn check_index(self, password: &str) -> IndexReport {
+        let positions = [usize::from(self.min), usize::from(self.max)];
+        let matched = positions
+            .iter()
+            .copied()
+            .filter(|&pos| {
+                pos.checked_sub(1)
+                    .and_then(|i| password.chars().nth(i))
+                    .map_or(false, |c| c == self.letter)
+            })
+            .collect();
+        IndexReport { positions, matched }
+    }
+
+    fn valid_count(self, password: &str) -> bool {
+        self.check_count(password).is_valid()
+    }
+
+    fn valid_index(self, password: &str) -> bool {
+        self.check_index(password).is_valid()
+    }
+}
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}", self.min, self.max, self.letter)
+    }
+}
+
+/// Outcome of the policy which limits how often the letter occurs.
+#[derive(Clone, Debug, PartialEq)]
+struct CountReport {
+    /// 1-based positions of the letter in the password.
+    occurrences: Vec<usize>,
+    min: u16,
+    max: u16,
+}
+impl CountReport {
+    fn count(&self) -> usize {
+        self.occurrences.len()
+    }
+
+    fn is_valid(&self) -> bool {
+        (usize::from(self.min)..=usize::from(self.max)).contains(&self.count())
+    }
+
+    /// Positions responsible for the password being invalid.
+    ///
+    /// If the letter occurs too rarely there's nothing to point at.
+    fn offending(&self) -> &[usize] {
+        if self.count() > usize::from(self.max) {
+            &self.occurrences
+        } else {
+            &[]
+        }
+    }
+}
+
+/// Outcome of the policy which requires the letter at exactly one of two positions.
+#[derive(Clone, Debug, PartialEq)]
+struct IndexReport {
+    /// 1-based positions which are checked.
+    positions: [usize; 2],
+    /// The checked positions which hold the letter.
+    matched: Vec<usize>,
+}
+impl IndexReport {
+    fn is_valid(&self) -> bool {
+        self.matched.len() == 1
+    }
+
+    /// Positions responsible for the password being invalid.
+    fn offending(&self) -> &[usize] {
+        match self.matched.len() {
+            0 => &self.positions,
+            1 => &[],
+            _ => &self.matched,
+        }
+    }
+}
+
+/// A rule passwords have to satisfy, given the policy of their entry.
+trait Rule {
+    fn check(&self, policy: Policy, password: &str) -> bool;
+}
+
+/// The letter has to occur between `min` and `max` times.
+struct Count;
+impl Rule for Count {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        policy.valid_count(password)
+    }
+}
+
+/// The letter has to be at exactly one of the positions `min` and `max`.
+struct Index;
+impl Rule for Index {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        policy.valid_index(password)
+    }
+}
+
+/// The password has to be between `min` and `max` characters long, regardless of the policy.
+struct Length {
+    min: usize,
+    max: usize,
+}
+impl Rule for Length {
+    fn check(&self, _policy: Policy, password: &str) -> bool {
+        (self.min..=self.max).contains(&password.chars().count())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CharClass {
+    Digit,
+    Lower,
+    Upper,
+    /// Anything that isn't alphanumeric.
+    Symbol,
+    /// The letter of the policy.
+    Letter,
+}
+impl CharClass {
+    fn from_input(s: &str) -> Option<Self> {
+        match s {
+            "digit" => Some(Self::Digit),
+            "lower" => Some(Self::Lower),
+            "upper" => Some(Self::Upper),
+            "symbol" => Some(Self::Symbol),
+            "letter" => Some(Self::Letter),
+            _ => None,
+        }
+    }
+
+    fn matches(self, policy: Policy, c: char) -> bool {
+        match self {
+            Self::Digit => c.is_ascii_digit(),
+            Self::Lower => c.is_lowercase(),
+            Self::Upper => c.is_uppercase(),
+            Self::Symbol => !c.is_alphanumeric(),
+            Self::Letter => c == policy.letter,
+        }
+    }
+}
+
+/// The password has to contain at least one character of the class.
+struct Contains(CharClass);
+impl Rule for Contains {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        password.chars().any(|c| self.0.matches(policy, c))
+    }
+}
+
+struct AllOf(Vec<Box<dyn Rule>>);
+impl Rule for AllOf {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        self.0.iter().all(|rule| rule.check(policy, password))
+    }
+}
+
+struct AnyOf(Vec<Box<dyn Rule>>);
+impl Rule for AnyOf {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        self.0.iter().any(|rule| rule.check(policy, password))
+    }
+}
+
+struct NoneOf(Vec<Box<dyn Rule>>);
+impl Rule for NoneOf {
+    fn check(&self, policy: Policy, password: &str) -> bool {
+        !self.0.iter().any(|rule| rule.check(policy, password))
+    }
+}
+
+/// Parse a rule from its textual form.
+///
+/// The syntax consists of the built-in rules `count`, `index`, `length(min-max)` and
+/// `contains(class)`, which can be combined using `all(...)`, `any(...)` and `none(...)`.
+/// The class is one of `digit`, `lower`, `upper`, `symbol` or `letter` (the policy letter).
+/// For example: `all(count, none(index), length(8-16), contains(digit))`.
+fn parse_rule(s: &str) -> Option<Box<dyn Rule>> {
+    let (rule, rest) = parse_rule_prefix(s)?;
+    if rest.trim().is_empty() {
+        Some(rule)
+    } else {
+        None
+    }
+}
+
+/// Parse the rule at the start of `s` and return it together with the remaining text.
+fn parse_rule_prefix(s: &str) -> Option<(Box<dyn Rule>, &str)> {
+    let s = s.trim_start();
+    let name_len = s
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(s.len());
+    let (name, rest) = s.split_at(name_len);
+    match name {
+        "count" => Some((Box::new(Count), rest)),
+        "index" => Some((Box::new(Index), rest)),
+        "length" => {
+            let (args, rest) = parse_args(rest)?;
+            let sep = args.find('-')?;
+            let rule = Length {
+                min: args[..sep].trim().parse().ok()?,
+                max: args[sep + 1..].trim().parse().ok()?,
+            };
+            if rule.min > rule.max {
+                return None;
+            }
+            Some((Box::new(rule), rest))
+        }
+        "contains" => {
+            let (args, rest) = parse_args(rest)?;
+            let class = CharClass::from_input(args.trim())?;
+            Some((Box::new(Contains(class)), rest))
+        }
+        "all" | "any" | "none" => {
+            let (rules, rest) = parse_rule_list(rest)?;
+            let rule: Box<dyn Rule> = match name {
+                "all" => Box::new(AllOf(rules)),
+                "any" => Box::new(AnyOf(rules)),
+                _ => Box::new(NoneOf(rules)),
+            };
+            Some((rule, rest))
+        }
+        _ => None,
+    }
+}
+
+/// Split `(args) rest` into `args` and `rest`.
+fn parse_args(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('(')?;
+    let end = s.find(')')?;
+    Some((&s[..end], &s[end + 1..]))
+}
+
+/// Parse a parenthesised, comma separated list of rules.
+fn parse_rule_list(s: &str) -> Option<(Vec<Box<dyn Rule>>, &str)> {
+    let mut rest = s.trim_start().strip_prefix('(')?;
+    let mut rules = Vec::new();
+    loop {
+        let (rule, after) = parse_rule_prefix(rest)?;
+        rules.push(rule);
+        let after = after.trim_start();
+        if let Some(after) = after.strip_prefix(',') {
+            rest = after;
+        } else {
+            return Some((rules, after.strip_prefix(')')?));
+        }
     }
 }
 
@@ -45,7 +301,7 @@ impl<'a> Entry<'a> {
         let password = captures.name("password")?.as_str();
 
         Some(Self {
-            policy: Policy { min, max, letter },
+            policy: Policy { letter, min, max },
             password,
         })
     }
@@ -57,11 +313,15 @@ impl<'a> Entry<'a> {
     fn valid_index(&self) -> bool {
         self.policy.valid_index(self.password)
     }
+
+    fn satisfies(&self, rule: &dyn Rule) -> bool {
+        rule.check(self.policy, self.password)
+    }
 }
 
 type Entries<'a> = Vec<Entry<'a>>;
 
-fn parse_input(s: &str) -> Option<Entries> {
+fn parse_input(s: &str) -> Option<Entries<'_>> {
     s.lines()
         .filter_map(|s| {
             let s = s.trim();
@@ -82,8 +342,8 @@ impl Solution {
     fn solve<'a>(entries: impl IntoIterator<Item = Entry<'a>>) -> Self {
         let (valid_count, valid_index) = entries.into_iter().fold((0, 0), |(c, i), entry| {
             (
-                c + entry.valid_count() as usize,
-                i + entry.valid_index() as usize,
+                c + usize::from(entry.valid_count()),
+                i + usize::from(entry.valid_index()),
             )
         });
         Self {
@@ -91,18 +351,294 @@ impl Solution {
             valid_index,
         }
     }
+
+    fn count_valid(entries: &[Entry], rule: &dyn Rule) -> usize {
+        entries.iter().filter(|entry| entry.satisfies(rule)).count()
+    }
+}
+
+/// Everything the page shows about a single entry.
+struct EntryReport {
+    policy: Policy,
+    password: String,
+    count: CountReport,
+    index: IndexReport,
+}
+impl EntryReport {
+    fn new(entry: &Entry) -> Self {
+        Self {
+            policy: entry.policy,
+            password: entry.password.to_owned(),
+            count: entry.policy.check_count(entry.password),
+            index: entry.policy.check_index(entry.password),
+        }
+    }
+}
+
+fn render_password(password: &str, letter: char, offending: &[usize]) -> Html {
+    let render_char = |(i, c): (usize, char)| {
+        let class = if offending.contains(&(i + 1)) {
+            "offending"
+        } else if c == letter {
+            "letter"
+        } else {
+            ""
+        };
+        html! { <span class=class>{ c }</span> }
+    };
+    html! {
+        <code>{ for password.chars().enumerate().map(render_char) }</code>
+    }
+}
+
+fn render_validity(valid: bool) -> Html {
+    if valid {
+        html! { <span class="passed">{ "valid" }</span> }
+    } else {
+        html! { <span class="failed">{ "invalid" }</span> }
+    }
+}
+
+pub enum Msg {
+    Solve,
+    SetRule(InputData),
+}
+
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    reports: Vec<EntryReport>,
+    /// Additional rule entered by the user.
+    rule: Option<Box<dyn Rule>>,
+    rule_invalid: bool,
+}
+impl Page {
+    fn render_summary(&self) -> Html {
+        if self.reports.is_empty() {
+            return html! {};
+        }
+
+        let total = self.reports.len();
+        let valid_count = self.reports.iter().filter(|r| r.count.is_valid()).count();
+        let valid_index = self.reports.iter().filter(|r| r.index.is_valid()).count();
+        html! {
+            <ul>
+                <li>
+                    { format!("count policy: {} of {} valid ", valid_count, total) }
+                    <CopyButton text=valid_count.to_string() />
+                </li>
+                <li>
+                    { format!("index policy: {} of {} valid ", valid_index, total) }
+                    <CopyButton text=valid_index.to_string() />
+                </li>
+                { self.render_rule_summary() }
+            </ul>
+        }
+    }
+
+    fn render_rule_summary(&self) -> Html {
+        let rule = match &self.rule {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        let valid = self
+            .reports
+            .iter()
+            .filter(|r| rule.check(r.policy, &r.password))
+            .count();
+        html! {
+            <li>
+                { format!("custom policy: {} of {} valid ", valid, self.reports.len()) }
+                <CopyButton text=valid.to_string() />
+            </li>
+        }
+    }
+
+    fn render_report(&self, report: &EntryReport) -> Html {
+        let EntryReport {
+            policy,
+            password,
+            count,
+            index,
+        } = report;
+        let count_text = format!(
+            "{} × {}, allowed {}-{}",
+            count.count(),
+            policy.letter,
+            count.min,
+            count.max
+        );
+        let index_text = if index.matched.is_empty() {
+            format!(
+                "neither {} nor {} is {}",
+                index.positions[0], index.positions[1], policy.letter
+            )
+        } else {
+            let matched = index
+                .matched
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" and ");
+            format!("{} at {}", policy.letter, matched)
+        };
+
+        html! {
+            <tr>
+                <td><code>{ policy }</code></td>
+                <td>{ render_password(password, policy.letter, count.offending()) }</td>
+                <td>{ count_text }</td>
+                <td>{ render_validity(count.is_valid()) }</td>
+                <td>{ render_password(password, policy.letter, index.offending()) }</td>
+                <td>{ index_text }</td>
+                <td>{ render_validity(index.is_valid()) }</td>
+                {
+                    if let Some(rule) = &self.rule {
+                        html! { <td>{ render_validity(rule.check(*policy, password)) }</td> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </tr>
+        }
+    }
+
+    fn render_reports(&self) -> Html {
+        if self.reports.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <table class="passwords">
+                <thead>
+                    <tr>
+                        <th>{ "policy" }</th>
+                        <th colspan="3">{ "count policy" }</th>
+                        <th colspan="3">{ "index policy" }</th>
+                        {
+                            if self.rule.is_some() {
+                                html! { <th>{ "custom policy" }</th> }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </tr>
+                </thead>
+                <tbody>
+                    { for self.reports.iter().map(|report| self.render_report(report)) }
+                </tbody>
+            </table>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            reports: Vec::new(),
+            rule: None,
+            rule_invalid: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                if let Some(entries) = parse_input(&raw_input) {
+                    self.reports = entries.iter().map(EntryReport::new).collect();
+                    input_area.set_custom_validity("");
+                    results::record(2, &raw_input);
+                } else {
+                    self.reports.clear();
+                    input_area.set_custom_validity("failed to parse");
+                }
+
+                true
+            }
+            Msg::SetRule(data) => {
+                let text = data.value.trim();
+                if text.is_empty() {
+                    self.rule = None;
+                    self.rule_invalid = false;
+                } else {
+                    self.rule = parse_rule(text);
+                    self.rule_invalid = self.rule.is_none();
+                }
+
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link,
+            input_area,
+            rule_invalid,
+            ..
+        } = &self;
+        let rule_class = if *rule_invalid { "invalid" } else { "" };
+        html! {
+            <>
+                <input
+                    type="text"
+                    class=rule_class
+                    placeholder="custom policy, e.g. all(count, length(8-16), contains(digit))"
+                    oninput=link.callback(Msg::SetRule)
+                />
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=2 input=input_area.clone() />
+                <LoadExample day=2 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_summary() }
+                { self.render_reports() }
+            </>
+        }
+    }
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(Solution::solve(parse_input(input)?).valid_count.to_string())
 }
 
+fn solve_second(input: &str) -> Option<String> {
+    Some(Solution::solve(parse_input(input)?).valid_index.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    1-3 a: abcde
+    1-3 b: cdefg
+    2-9 c: ccccccccc
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("2"), Some("1")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        1-3 a: abcde
-        1-3 b: cdefg
-        2-9 c: ccccccccc
-    "#;
-
     fn example_input() -> Entries<'static> {
         parse_input(EXAMPLE_INPUT).expect("failed to parse input")
     }
@@ -113,4 +649,94 @@ mod tests {
         assert_eq!(sol.valid_count, 2);
         assert_eq!(sol.valid_index, 1);
     }
+
+    #[test]
+    fn count_report() {
+        let entries = example_input();
+        let report = entries[0].policy.check_count(entries[0].password);
+        assert_eq!(report.occurrences, vec![1]);
+        assert!(report.is_valid());
+
+        let report = entries[1].policy.check_count(entries[1].password);
+        assert_eq!(report.count(), 0);
+        assert!(!report.is_valid());
+        assert!(report.offending().is_empty());
+
+        let entry = Entry::from_str("1-2 a: abaca").expect("failed to parse entry");
+        let report = entry.policy.check_count(entry.password);
+        assert_eq!(report.offending(), &[1, 3, 5]);
+    }
+
+    #[test]
+    fn index_report() {
+        let entries = example_input();
+        let report = entries[0].policy.check_index(entries[0].password);
+        assert_eq!(report.matched, vec![1]);
+        assert!(report.is_valid());
+
+        let report = entries[1].policy.check_index(entries[1].password);
+        assert!(report.matched.is_empty());
+        assert_eq!(report.offending(), &[1, 3]);
+
+        let report = entries[2].policy.check_index(entries[2].password);
+        assert_eq!(report.matched, vec![2, 9]);
+        assert_eq!(report.offending(), &[2, 9]);
+    }
+
+    #[test]
+    fn rules() {
+        let entries = example_input();
+        let count_valid = |rule: &str| {
+            let rule = parse_rule(rule).expect("failed to parse rule");
+            Solution::count_valid(&entries, rule.as_ref())
+        };
+        assert_eq!(count_valid("count"), 2);
+        assert_eq!(count_valid("index"), 1);
+        assert_eq!(count_valid("all(count, index)"), 1);
+        assert_eq!(count_valid("any(count, index)"), 2);
+        assert_eq!(count_valid("none(count, index)"), 1);
+        assert_eq!(count_valid(" all( none(index) ,length(5-5)) "), 1);
+        assert_eq!(count_valid("length(6-9)"), 1);
+        assert_eq!(count_valid("contains(letter)"), 2);
+        assert_eq!(count_valid("none(contains(letter))"), 1);
+        assert_eq!(count_valid("contains(lower)"), 3);
+        assert_eq!(
+            count_valid("any(contains(digit), contains(upper), contains(symbol))"),
+            0
+        );
+    }
+
+    #[test]
+    fn char_classes() {
+        let entry = Entry::from_str("1-3 a: Pass_w0rd").expect("failed to parse entry");
+        let satisfies = |rule: &str| {
+            let rule = parse_rule(rule).expect("failed to parse rule");
+            entry.satisfies(rule.as_ref())
+        };
+        assert!(satisfies("contains(digit)"));
+        assert!(satisfies("contains(lower)"));
+        assert!(satisfies("contains(upper)"));
+        assert!(satisfies("contains(symbol)"));
+        assert!(satisfies("contains(letter)"));
+        assert!(!satisfies("all(count, none(contains(letter)))"));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        for rule in &[
+            "",
+            "counts",
+            "all()",
+            "all(count",
+            "any(count,)",
+            "length(5)",
+            "length(a-b)",
+            "length(16-8)",
+            "contains()",
+            "contains(digits)",
+            "count index",
+        ] {
+            assert!(parse_rule(rule).is_none(), "{:?} parsed", rule);
+        }
+    }
 }
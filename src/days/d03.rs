@@ -1,4 +1,113 @@
-use std::collections::HashSet;
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{clipboard, results, share},
+    utils::ResultExt,
+};
+use std::{collections::HashSet, convert::TryFrom, fmt, ops::RangeInclusive};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+/// Movement per step, a negative `right` moves left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Slope {
+    right: isize,
+    down: usize,
+}
+impl Slope {
+    const fn new(right: isize, down: usize) -> Self {
+        Self { right, down }
+    }
+
+    /// Parse a slope in the form `right,down`.
+    ///
+    /// `down` has to be positive, otherwise the toboggan would never arrive.
+    fn from_input(s: &str) -> Option<Self> {
+        let mut it = s.split(',');
+        let right = it.next()?.trim().parse().ok()?;
+        let down = it.next()?.trim().parse().ok()?;
+        if down == 0 || it.next().is_some() {
+            return None;
+        }
+
+        Some(Self { right, down })
+    }
+}
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.right, self.down)
+    }
+}
+
+/// Parse a list of slopes separated by `;` or newlines.
+fn parse_slopes(s: &str) -> Option<Vec<Slope>> {
+    s.split(&[';', '\n'][..])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(Slope::from_input)
+        .collect()
+}
+
+/// The slopes checked by the second part.
+const DEFAULT_SLOPES: &[Slope] = &[
+    Slope::new(1, 1),
+    Slope::new(3, 1),
+    Slope::new(5, 1),
+    Slope::new(7, 1),
+    Slope::new(1, 2),
+];
+
+/// Range of slopes to search.
+struct SlopeBounds {
+    right: RangeInclusive<isize>,
+    down: RangeInclusive<usize>,
+}
+impl SlopeBounds {
+    /// Searching more slopes than this would freeze the page.
+    const MAX_SLOPES: usize = 10_000;
+
+    /// Number of slopes within the bounds, saturating at `usize::MAX`.
+    fn len(&self) -> usize {
+        let (right_start, right_end) = (*self.right.start(), *self.right.end());
+        let rights = if right_start > right_end {
+            0
+        } else {
+            right_end
+                .checked_sub(right_start)
+                .and_then(|diff| usize::try_from(diff).ok())
+                .map_or(usize::MAX, |diff| diff.saturating_add(1))
+        };
+        let down_start = (*self.down.start()).max(1);
+        let downs = self.down.end().saturating_add(1).saturating_sub(down_start);
+        rights.saturating_mul(downs)
+    }
+}
+
+/// The slope bounds contain more than [`SlopeBounds::MAX_SLOPES`] slopes.
+#[derive(Debug, PartialEq)]
+struct TooManySlopes {
+    slopes: usize,
+}
+impl fmt::Display for TooManySlopes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the bounds contain {} slopes, narrow them down to at most {}",
+            self.slopes,
+            SlopeBounds::MAX_SLOPES
+        )
+    }
+}
+
+/// Widest map [`InfiniteGrid::draw_paths`] draws.
+const MAX_DRAWN_COLUMNS: usize = 500;
+
+#[derive(Default)]
+struct Drawing {
+    lines: Vec<String>,
+    /// Whether some paths leave the drawn part of the map.
+    truncated: bool,
+}
 
 struct InfiniteGrid {
     trees: HashSet<(usize, usize)>,
@@ -19,7 +128,7 @@ impl InfiniteGrid {
                 width = width.max(line.len());
                 height = y + 1;
 
-                line.chars().enumerate().flat_map(move |(x, c)| {
+                line.chars().enumerate().filter_map(move |(x, c)| {
                     if c == Self::TREE_CHAR {
                         Some((x, y))
                     } else {
@@ -36,53 +145,420 @@ impl InfiniteGrid {
         }
     }
 
+    /// Wrap a column of the infinitely repeating map into the input.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn wrap(&self, x: i128) -> usize {
+        // the result is in `0..width`, so it fits
+        x.rem_euclid(self.width.max(1) as i128) as usize
+    }
+
     fn has_tree(&self, x: usize, y: usize) -> bool {
-        self.trees.contains(&(x % self.width, y))
+        self.trees.contains(&(x, y))
     }
 
-    fn count_trees(&self, (right, down): (usize, usize)) -> usize {
-        let mut count = 0;
-        let mut x = 0;
-        for y in (0..self.height).step_by(down) {
-            if self.has_tree(x, y) {
-                count += 1;
-            }
-            x += right;
+    /// Positions visited when following `slope` from the top left corner to the bottom.
+    ///
+    /// The columns are wrapped into the input.
+    fn path(&self, slope: Slope) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let shift = self.wrap(slope.right as i128);
+        let width = self.width.max(1);
+        let xs = std::iter::successors(Some(0), move |x| Some((x + shift) % width));
+        xs.zip((0..self.height).step_by(slope.down))
+    }
+
+    /// Like [`path`], but with the columns of the repeated map.
+    fn unwrapped_path(&self, slope: Slope) -> impl Iterator<Item = (i128, usize)> {
+        // a step count and an `isize` can't overflow an `i128`
+        let xs = (0..).map(move |step: i128| step * slope.right as i128);
+        xs.zip((0..self.height).step_by(slope.down))
+    }
+
+    fn count_trees(&self, slope: Slope) -> usize {
+        self.path(slope)
+            .filter(|&(x, y)| self.has_tree(x, y))
+            .count()
+    }
+
+    /// Draw the map with the paths of all `slopes` on it.
+    ///
+    /// The map is repeated horizontally as far as the paths reach, but at most
+    /// [`MAX_DRAWN_COLUMNS`] wide (and always at least the first copy of the map).
+    /// Like in the puzzle text, trees which are hit are marked with `X` and open squares with `O`.
+    fn draw_paths(&self, slopes: &[Slope]) -> Drawing {
+        if self.width == 0 {
+            return Drawing::default();
+        }
+
+        let visited = slopes
+            .iter()
+            .flat_map(|&slope| self.unwrapped_path(slope))
+            .collect::<HashSet<_>>();
+        let width = self.width as i128;
+        let min_tile = visited
+            .iter()
+            .map(|&(x, _)| x)
+            .min()
+            .unwrap_or(0)
+            .div_euclid(width);
+        let max_tile = visited
+            .iter()
+            .map(|&(x, _)| x)
+            .max()
+            .unwrap_or(0)
+            .div_euclid(width);
+        // always draw whole tiles, splitting them between both sides if the paths go both ways
+        let max_tiles = (MAX_DRAWN_COLUMNS as i128 / width).max(1);
+        let left_budget = if max_tile > 0 {
+            (max_tiles - 1) / 2
+        } else {
+            max_tiles - 1
+        };
+        let left = (-min_tile).min(left_budget);
+        let right = max_tile.min(max_tiles - 1 - left);
+        let start = -left * width;
+        let end = (right + 1) * width;
+
+        let lines = (0..self.height)
+            .map(|y| {
+                (start..end)
+                    .map(
+                        |x| match (visited.contains(&(x, y)), self.has_tree(self.wrap(x), y)) {
+                            (true, true) => 'X',
+                            (true, false) => 'O',
+                            (false, true) => Self::TREE_CHAR,
+                            (false, false) => '.',
+                        },
+                    )
+                    .collect()
+            })
+            .collect();
+        Drawing {
+            lines,
+            truncated: left < -min_tile || right < max_tile,
         }
-        count
     }
+
+    /// Product of the trees hit on each slope, `None` if it overflows.
+    fn product_of(&self, slopes: &[Slope]) -> Option<u64> {
+        product(slopes.iter().map(|&slope| self.count_trees(slope)))
+    }
+
+    /// Find the slope within `bounds` which hits the fewest trees.
+    ///
+    /// Ties are resolved in favour of the smaller `down` and then the smaller `right`.
+    /// Returns `Ok(None)` if the bounds are empty.
+    fn best_slope(&self, bounds: &SlopeBounds) -> Result<Option<(Slope, usize)>, TooManySlopes> {
+        let slopes = bounds.len();
+        if slopes > SlopeBounds::MAX_SLOPES {
+            return Err(TooManySlopes { slopes });
+        }
+
+        let downs = bounds.down.clone().filter(|&down| down > 0);
+        let best = downs
+            .flat_map(|down| {
+                bounds
+                    .right
+                    .clone()
+                    .map(move |right| Slope::new(right, down))
+            })
+            .map(|slope| (slope, self.count_trees(slope)))
+            .min_by_key(|&(_, trees)| trees);
+        Ok(best)
+    }
+}
+
+fn product(counts: impl IntoIterator<Item = usize>) -> Option<u64> {
+    counts.into_iter().try_fold(1_u64, |product, count| {
+        product.checked_mul(u64::try_from(count).ok()?)
+    })
 }
 
 fn first_part(grid: &InfiniteGrid) -> usize {
-    grid.count_trees((3, 1))
+    grid.count_trees(Slope::new(3, 1))
+}
+
+fn second_part(grid: &InfiniteGrid) -> Option<u64> {
+    grid.product_of(DEFAULT_SLOPES)
+}
+
+/// Tree counts for the slopes requested on the page.
+struct Report {
+    counts: Vec<(Slope, usize)>,
+    /// `None` if the product overflows.
+    product: Option<u64>,
+    best: Result<Option<(Slope, usize)>, TooManySlopes>,
+    map: Drawing,
+}
+impl Report {
+    fn new(grid: &InfiniteGrid, slopes: &[Slope], bounds: &SlopeBounds) -> Self {
+        let counts = slopes
+            .iter()
+            .map(|&slope| (slope, grid.count_trees(slope)))
+            .collect::<Vec<_>>();
+        let product = product(counts.iter().map(|&(_, trees)| trees));
+        Self {
+            counts,
+            product,
+            best: grid.best_slope(bounds),
+            map: grid.draw_paths(slopes),
+        }
+    }
+}
+
+/// Render a line of the map, highlighting the squares on a path.
+fn render_map_line(line: &str) -> Html {
+    let mut nodes = Vec::new();
+    let mut plain = String::new();
+    for c in line.chars() {
+        let class = match c {
+            'X' => "hit",
+            'O' => "miss",
+            _ => {
+                plain.push(c);
+                continue;
+            }
+        };
+        if !plain.is_empty() {
+            nodes.push(html! { { std::mem::take(&mut plain) } });
+        }
+        nodes.push(html! { <span class=class>{ c }</span> });
+    }
+    plain.push('\n');
+    nodes.push(html! { { plain } });
+
+    html! { <>{ for nodes }</> }
+}
+
+#[derive(Clone, Copy)]
+pub enum Bound {
+    RightMin,
+    RightMax,
+    DownMin,
+    DownMax,
+}
+
+pub enum Msg {
+    Solve,
+    SetBound(Bound, InputData),
+    CopyMap,
 }
 
-fn second_part(grid: &InfiniteGrid) -> usize {
-    grid.count_trees((1, 1))
-        * grid.count_trees((1, 2))
-        * grid.count_trees((3, 1))
-        * grid.count_trees((5, 1))
-        * grid.count_trees((7, 1))
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    slopes_area: NodeRef,
+    bounds: SlopeBounds,
+    report: Option<Report>,
 }
+impl Page {
+    fn set_bound(&mut self, bound: Bound, value: &str) -> Option<()> {
+        let bounds = &mut self.bounds;
+        match bound {
+            Bound::RightMin => bounds.right = value.parse().ok()?..=*bounds.right.end(),
+            Bound::RightMax => bounds.right = *bounds.right.start()..=value.parse().ok()?,
+            Bound::DownMin => bounds.down = value.parse().ok()?..=*bounds.down.end(),
+            Bound::DownMax => bounds.down = *bounds.down.start()..=value.parse().ok()?,
+        }
+        Some(())
+    }
+
+    fn render_product(product: Option<u64>) -> Html {
+        if let Some(product) = product {
+            html! {
+                <>
+                    <code>{ product }</code>
+                    <CopyButton text=product.to_string() />
+                </>
+            }
+        } else {
+            html! { <span class="overflow">{ "product overflows" }</span> }
+        }
+    }
+
+    fn render_report(&self) -> Html {
+        let report = match &self.report {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        let render_count = |&(slope, trees): &(Slope, usize)| {
+            html! {
+                <li>{ format!("right {}, down {}: {} trees", slope.right, slope.down, trees) }</li>
+            }
+        };
+        let best = match &report.best {
+            Ok(Some((slope, trees))) => format!(
+                "fewest trees: right {}, down {} with {} trees",
+                slope.right, slope.down, trees
+            ),
+            Ok(None) => "no slope within the bounds".to_owned(),
+            Err(err) => err.to_string(),
+        };
+        let truncated = if report.map.truncated {
+            let columns = report.map.lines.first().map_or(0, String::len);
+            html! { <p>{ format!("the paths leave the map, only {} columns are drawn", columns) }</p> }
+        } else {
+            html! {}
+        };
+        html! {
+            <>
+                <ul>
+                    { for report.counts.iter().map(render_count) }
+                </ul>
+                <p>
+                    { "product: " }
+                    { Self::render_product(report.product) }
+                </p>
+                <p>{ best }</p>
+                <button onclick=self.link.callback(|_| Msg::CopyMap)>{ "copy map" }</button>
+                { truncated }
+                <pre class="toboggan">
+                    { for report.map.lines.iter().map(|line| render_map_line(line)) }
+                </pre>
+            </>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            slopes_area: NodeRef::default(),
+            bounds: SlopeBounds {
+                right: -7..=7,
+                down: 1..=2,
+            },
+            report: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let slopes_area = self.slopes_area.cast::<HtmlTextAreaElement>().unwrap();
+                let slopes = if let Some(slopes) = parse_slopes(&slopes_area.value()) {
+                    slopes_area.set_custom_validity("");
+                    slopes
+                } else {
+                    slopes_area.set_custom_validity("expected slopes like 3,1");
+                    self.report = None;
+                    return true;
+                };
+
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                let grid = InfiniteGrid::from_input(&raw_input);
+                if grid.width == 0 {
+                    self.report = None;
+                    input_area.set_custom_validity("map is empty");
+                } else {
+                    self.report = Some(Report::new(&grid, &slopes, &self.bounds));
+                    input_area.set_custom_validity("");
+                    results::record(3, &raw_input);
+                }
+
+                true
+            }
+            Msg::SetBound(bound, data) => self.set_bound(bound, &data.value).is_some(),
+            Msg::CopyMap => {
+                if let Some(report) = &self.report {
+                    clipboard::write_text(&report.map.lines.join("\n"))
+                        .ok_or_log("failed to copy to clipboard");
+                }
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            let slopes_area = self.slopes_area.cast::<HtmlTextAreaElement>().unwrap();
+            let slopes = DEFAULT_SLOPES
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            slopes_area.set_value(&slopes);
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link,
+            input_area,
+            slopes_area,
+            bounds,
+            ..
+        } = &self;
+        html! {
+            <>
+                <label>
+                    <span>{ "slopes (right,down separated by ; or new lines)" }</span>
+                    <textarea class="slopes" ref=slopes_area.clone() />
+                </label>
+                <div>
+                    <span>{ "search right " }</span>
+                    <input type="number" value=bounds.right.start() oninput=link.callback(|data| Msg::SetBound(Bound::RightMin, data)) />
+                    <span>{ " to " }</span>
+                    <input type="number" value=bounds.right.end() oninput=link.callback(|data| Msg::SetBound(Bound::RightMax, data)) />
+                    <span>{ ", down " }</span>
+                    <input type="number" min="1" value=bounds.down.start() oninput=link.callback(|data| Msg::SetBound(Bound::DownMin, data)) />
+                    <span>{ " to " }</span>
+                    <input type="number" min="1" value=bounds.down.end() oninput=link.callback(|data| Msg::SetBound(Bound::DownMax, data)) />
+                </div>
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=3 input=input_area.clone() />
+                <LoadExample day=3 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_report() }
+            </>
+        }
+    }
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+#[allow(clippy::unnecessary_wraps)]
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&InfiniteGrid::from_input(input)).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&InfiniteGrid::from_input(input)).map(|product| product.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    ..##.......
+    #...#...#..
+    .#....#..#.
+    ..#.#...#.#
+    .#...##..#.
+    ..#.##.....
+    .#.#.#....#
+    .#........#
+    #.##...#...
+    #...##....#
+    .#..#...#.#
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("7"), Some("336")],
+}];
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        ..##.......
-        #...#...#..
-        .#....#..#.
-        ..#.#...#.#
-        .#...##..#.
-        ..#.##.....
-        .#.#.#....#
-        .#........#
-        #.##...#...
-        #...##....#
-        .#..#...#.#
-    "#;
-
     fn example_input() -> InfiniteGrid {
         InfiniteGrid::from_input(EXAMPLE_INPUT)
     }
@@ -95,6 +571,171 @@ mod tests {
     #[test]
     fn second() {
         let sol = second_part(&example_input());
-        assert_eq!(sol, 336);
+        assert_eq!(sol, Some(336));
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn leftward() {
+        let grid = InfiniteGrid::from_input("#..\n.#.\n..#");
+        assert_eq!(grid.count_trees(Slope::new(1, 1)), 3);
+        assert_eq!(grid.count_trees(Slope::new(-1, 1)), 1);
+
+        // moving left wraps around just like moving right
+        let grid = example_input();
+        for right in 1..grid.width {
+            let wrapped = (grid.width - right) as isize;
+            assert_eq!(
+                grid.count_trees(Slope::new(-wrapped, 1)),
+                grid.count_trees(Slope::new(right as isize, 1))
+            );
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn huge_slopes() {
+        let grid = example_input();
+        let width = grid.width as isize;
+        for &right in &[isize::MAX, isize::MIN, 1_000_000_000, -1_000_000_001] {
+            assert_eq!(
+                grid.count_trees(Slope::new(right, 1)),
+                grid.count_trees(Slope::new(right.rem_euclid(width), 1)),
+                "right {}",
+                right
+            );
+        }
+        let slope = Slope::from_input("1000000000,1").expect("failed to parse slope");
+        assert_eq!(grid.path(slope).count(), grid.height);
+    }
+
+    #[test]
+    fn slopes() {
+        assert_eq!(
+            parse_slopes("1,1; 3, 1\n-2,2;").expect("failed to parse slopes"),
+            vec![Slope::new(1, 1), Slope::new(3, 1), Slope::new(-2, 2)]
+        );
+        assert!(parse_slopes("1,0").is_none());
+        assert!(parse_slopes("1").is_none());
+        assert!(parse_slopes("1,1,1").is_none());
+
+        let grid = example_input();
+        assert_eq!(grid.product_of(DEFAULT_SLOPES), Some(336));
+    }
+
+    #[test]
+    fn product_overflow() {
+        assert_eq!(product(vec![4000, 5000, 6000]), Some(120_000_000_000));
+        assert_eq!(product(vec![1 << 20; 4]), None);
+        assert_eq!(product(Vec::new()), Some(1));
+    }
+
+    #[test]
+    fn best_slope() {
+        let grid = example_input();
+        let bounds = SlopeBounds {
+            right: -3..=3,
+            down: 0..=1,
+        };
+        let (slope, trees) = grid
+            .best_slope(&bounds)
+            .expect("too many slopes")
+            .expect("failed to search");
+        assert_eq!(trees, grid.count_trees(slope));
+        for right in bounds.right.clone() {
+            assert!(grid.count_trees(Slope::new(right, 1)) >= trees);
+        }
+
+        let empty = SlopeBounds {
+            right: 0..=1,
+            down: 0..=0,
+        };
+        assert_eq!(grid.best_slope(&empty), Ok(None));
+    }
+
+    #[test]
+    fn search_limit() {
+        let bounds = SlopeBounds {
+            right: -1_000_000..=1_000_000,
+            down: 1..=1_000_000,
+        };
+        assert_eq!(bounds.len(), 2_000_001 * 1_000_000);
+        assert_eq!(
+            example_input().best_slope(&bounds),
+            Err(TooManySlopes {
+                slopes: 2_000_001 * 1_000_000
+            })
+        );
+
+        let extreme = SlopeBounds {
+            right: isize::MIN..=isize::MAX,
+            down: 0..=usize::MAX,
+        };
+        assert_eq!(extreme.len(), usize::MAX);
+        assert_eq!(
+            SlopeBounds {
+                right: RangeInclusive::new(5, 4),
+                down: 1..=2
+            }
+            .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn draw_paths() {
+        let grid = example_input();
+        let Drawing {
+            lines: map,
+            truncated,
+        } = grid.draw_paths(&[Slope::new(3, 1)]);
+        assert!(!truncated);
+        // 10 steps of 3 need 31 columns, which are covered by three tiles
+        assert_eq!(map.len(), grid.height);
+        assert!(map.iter().all(|line| line.len() == 3 * grid.width));
+        assert_eq!(&map[0][..11], "O.##.......");
+        assert_eq!(&map[1][..11], "#..O#...#..");
+        assert_eq!(&map[2][..11], ".#....X..#.");
+        let hits = map
+            .iter()
+            .map(|line| line.matches('X').count())
+            .sum::<usize>();
+        assert_eq!(hits, 7);
+
+        // leftward paths extend the map to the left
+        let map = grid
+            .draw_paths(&[Slope::new(-1, 1), Slope::new(1, 1)])
+            .lines;
+        assert!(map.iter().all(|line| line.len() == 2 * grid.width));
+        assert_eq!(&map[1][..11], "#...#...#.O");
+        assert_eq!(&map[1][11..13], "#O");
+    }
+
+    #[test]
+    fn draw_wide_paths() {
+        let grid = example_input();
+        let tiles = MAX_DRAWN_COLUMNS / grid.width;
+
+        let drawing = grid.draw_paths(&[Slope::new(1000, 1)]);
+        assert!(drawing.truncated);
+        assert!(drawing
+            .lines
+            .iter()
+            .all(|line| line.len() == tiles * grid.width));
+        assert!(drawing.lines[0].starts_with('O'));
+
+        let drawing = grid.draw_paths(&[Slope::new(-1000, 1), Slope::new(1000, 1)]);
+        assert!(drawing.truncated);
+        assert!(drawing
+            .lines
+            .iter()
+            .all(|line| line.len() <= MAX_DRAWN_COLUMNS));
+        // the start of the paths stays visible
+        let start = (tiles - 1) / 2 * grid.width;
+        assert_eq!(&drawing.lines[0][start..=start], "O");
+
+        let drawing = grid.draw_paths(&[Slope::new(isize::MIN, 1)]);
+        assert!(drawing.truncated);
+        assert!(drawing.lines[0].ends_with("O.##......."));
     }
 }
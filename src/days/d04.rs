@@ -1,212 +1,886 @@
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::{ops::RangeBounds, str::FromStr};
+use std::{collections::HashMap, fmt};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
 
-fn numeric_and_in_range<T>(s: Option<&str>, range: impl RangeBounds<T>) -> bool
-where
-    T: FromStr + PartialOrd,
-{
-    let v = match s.and_then(|s| s.parse().ok()) {
-        Some(v) => v,
-        None => return false,
-    };
+/// The rules from the puzzle text, see [`Schema::from_input`] for the syntax.
+const DEFAULT_SCHEMA: &str = r#"
+    byr number 1920-2002
+    iyr number 2010-2020
+    eyr number 2020-2030
+    hgt number 150-193cm 59-76in
+    hcl regex #[0-9a-f]{6}
+    ecl enum amb blu brn gry grn hzl oth
+    pid digits 9
+    cid optional any
+"#;
 
-    range.contains(&v)
+/// Inclusive range of numbers followed by a unit, which may be empty.
+#[derive(Debug)]
+struct NumberRange {
+    min: u32,
+    max: u32,
+    unit: String,
 }
+impl NumberRange {
+    fn from_input(s: &str) -> Result<Self, &'static str> {
+        const INVALID: &str = "expected ranges like `150-193cm`";
 
-#[derive(Debug, Default)]
-struct Passport<'a> {
-    byr: Option<&'a str>,
-    iyr: Option<&'a str>,
-    eyr: Option<&'a str>,
-    hgt: Option<&'a str>,
-    hcl: Option<&'a str>,
-    ecl: Option<&'a str>,
-    pid: Option<&'a str>,
-    cid: Option<&'a str>,
-}
-impl<'a> Passport<'a> {
-    fn set_key(&mut self, key: &str, value: &'a str) -> bool {
-        let slot = match key {
-            "byr" => &mut self.byr,
-            "iyr" => &mut self.iyr,
-            "eyr" => &mut self.eyr,
-            "hgt" => &mut self.hgt,
-            "hcl" => &mut self.hcl,
-            "ecl" => &mut self.ecl,
-            "pid" => &mut self.pid,
-            "cid" => &mut self.cid,
-            _ => return false,
+        let unit_start = s.rfind(|c: char| c.is_ascii_digit()).map_or(0, |i| i + 1);
+        let (range, unit) = s.split_at(unit_start);
+        let sep = range.find('-').ok_or(INVALID)?;
+        let range = Self {
+            min: range[..sep].parse().map_err(|_| INVALID)?,
+            max: range[sep + 1..].parse().map_err(|_| INVALID)?,
+            unit: unit.to_owned(),
         };
+        if range.min > range.max {
+            return Err("range starts after it ends");
+        }
+        Ok(range)
+    }
 
-        slot.replace(value);
-        true
+    fn check(&self, value: &str) -> Result<(), FieldError> {
+        let number = value
+            .strip_suffix(self.unit.as_str())
+            .ok_or(FieldError::BadFormat)?;
+        let number = number.parse().map_err(|_| FieldError::NotNumeric)?;
+        if (self.min..=self.max).contains(&number) {
+            Ok(())
+        } else {
+            Err(FieldError::OutOfRange)
+        }
     }
+}
 
-    fn set_from_pair(&mut self, pair: &'a str) -> bool {
-        let mut parts = pair.split(':');
-        let key = match parts.next() {
-            Some(v) => v,
-            None => return false,
+/// Reason why the value of a field isn't accepted.
+///
+/// The order goes from least to most specific.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum FieldError {
+    Missing,
+    /// The field has the wrong format, for example a height without unit.
+    BadFormat,
+    NotNumeric,
+    OutOfRange,
+}
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Missing => "missing",
+            Self::NotNumeric => "not numeric",
+            Self::OutOfRange => "out of range",
+            Self::BadFormat => "bad format",
         };
-        let value = match parts.next() {
-            Some(v) => v,
-            None => return false,
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug)]
+enum Rule {
+    /// Any value is accepted.
+    Any,
+    /// A number within any of the ranges.
+    Number(Vec<NumberRange>),
+    /// The whole value has to match the regular expression.
+    Regex(Regex),
+    /// One of the listed values.
+    Enum(Vec<String>),
+    /// Exactly this many characters.
+    Length(usize),
+    /// Exactly this many decimal digits, leading zeroes included.
+    Digits(usize),
+}
+impl Rule {
+    fn from_input(name: &str, args: &str) -> Result<Self, &'static str> {
+        let rule = match name {
+            "any" => Self::Any,
+            "number" => Self::Number(
+                args.split_whitespace()
+                    .map(NumberRange::from_input)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "regex" => Self::Regex(
+                Regex::new(&format!("^(?:{})$", args)).map_err(|_| "invalid regular expression")?,
+            ),
+            "enum" => Self::Enum(args.split_whitespace().map(str::to_owned).collect()),
+            "length" => Self::Length(args.parse().map_err(|_| "expected a length")?),
+            "digits" => Self::Digits(args.parse().map_err(|_| "expected a digit count")?),
+            _ => return Err("unknown rule"),
+        };
+        Ok(rule)
+    }
+
+    fn check(&self, value: &str) -> Result<(), FieldError> {
+        let valid = match self {
+            Self::Any => true,
+            Self::Number(ranges) => {
+                // report the most specific error of all ranges
+                let mut error = FieldError::BadFormat;
+                for range in ranges {
+                    match range.check(value) {
+                        Ok(()) => return Ok(()),
+                        Err(err) => error = error.max(err),
+                    }
+                }
+                return Err(error);
+            }
+            Self::Regex(re) => re.is_match(value),
+            Self::Enum(values) => values.iter().any(|v| v == value),
+            Self::Length(len) => value.chars().count() == *len,
+            Self::Digits(len) => {
+                if !value.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(FieldError::NotNumeric);
+                }
+                value.len() == *len
+            }
         };
-        if parts.next().is_some() {
-            false
+
+        if valid {
+            Ok(())
         } else {
-            self.set_key(key, value)
+            Err(FieldError::BadFormat)
         }
     }
 
-    fn has_required_fields(&self) -> bool {
-        let Self {
-            byr,
-            iyr,
-            eyr,
-            hgt,
-            hcl,
-            ecl,
-            pid,
-            cid: _,
-        } = self;
-        byr.is_some()
-            && iyr.is_some()
-            && eyr.is_some()
-            && hgt.is_some()
-            && hcl.is_some()
-            && ecl.is_some()
-            && pid.is_some()
-    }
-
-    fn is_valid(&self) -> bool {
+    fn matches(&self, value: &str) -> bool {
+        self.check(value).is_ok()
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    key: String,
+    required: bool,
+    rule: Rule,
+}
+impl Field {
+    fn check(&self, passport: &Passport) -> Result<(), FieldError> {
+        match passport.get(&self.key) {
+            Some(value) => self.rule.check(value),
+            None if self.required => Err(FieldError::Missing),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct SchemaError {
+    /// 1-based line number.
+    line: usize,
+    message: &'static str,
+}
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Rules describing which fields a passport must have and what values they may hold.
+#[derive(Debug)]
+struct Schema {
+    fields: Vec<Field>,
+}
+impl Schema {
+    /// Parse a schema with one field per line.
+    ///
+    /// Each line has the form `<key> [optional] <rule> <arguments>` where the rule is one of:
+    /// - `any`
+    /// - `number <min>-<max>[unit]...`
+    /// - `regex <pattern>`
+    /// - `enum <value>...`
+    /// - `length <n>`
+    /// - `digits <n>`
+    ///
+    /// Empty lines and lines starting with `//` are ignored.
+    fn from_input(s: &str) -> Result<Self, SchemaError> {
+        let mut fields = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let err = |message| SchemaError {
+                line: i + 1,
+                message,
+            };
+            let mut words = line.splitn(2, char::is_whitespace);
+            let key = words.next().unwrap_or_default();
+            let mut rest = words.next().unwrap_or_default().trim_start();
+            let required = match rest.strip_prefix("optional") {
+                Some(after) => {
+                    rest = after.trim_start();
+                    false
+                }
+                None => true,
+            };
+            let mut words = rest.splitn(2, char::is_whitespace);
+            let name = words.next().unwrap_or_default();
+            let args = words.next().unwrap_or_default().trim();
+            if name.is_empty() {
+                return Err(err("missing rule"));
+            }
+            if fields.iter().any(|field: &Field| field.key == key) {
+                return Err(err("duplicate field"));
+            }
+
+            fields.push(Field {
+                key: key.to_owned(),
+                required,
+                rule: Rule::from_input(name, args).map_err(err)?,
+            });
+        }
+
+        Ok(Self { fields })
+    }
+
+    fn get_default() -> &'static Self {
         lazy_static! {
-            static ref HCL_RE: Regex = Regex::new(r#"^#[0-9a-f]{6}$"#).unwrap();
-        }
-
-        let hgt_valid = self.hgt.map_or(false, |hgt| {
-            hgt.strip_suffix("cm")
-                .map_or(false, |v| numeric_and_in_range(Some(v), 150..=193))
-                || hgt
-                    .strip_suffix("in")
-                    .map_or(false, |v| numeric_and_in_range(Some(v), 59..=76))
-        });
-        let hcl_valid = self.hcl.map_or(false, |hcl| HCL_RE.is_match(hcl));
-
-        hgt_valid
-            && hcl_valid
-            && numeric_and_in_range(self.byr, 1920..=2002)
-            && numeric_and_in_range(self.iyr, 2010..=2020)
-            && numeric_and_in_range(self.eyr, 2020..=2030)
-            && matches!(
-                self.ecl.unwrap_or_default(),
-                "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"
-            )
-            && self.pid.map_or(false, |pid| pid.len() == 9)
-    }
-}
-
-fn parse_input(inp: &str) -> Option<Vec<Passport>> {
-    let inp = inp.trim();
-    let mut passports = Vec::default();
+            static ref SCHEMA: Schema =
+                Schema::from_input(DEFAULT_SCHEMA).expect("default schema is invalid");
+        }
+
+        &SCHEMA
+    }
+
+    fn knows_key(&self, key: &str) -> bool {
+        self.fields.iter().any(|field| field.key == key)
+    }
+
+    /// Every field of the passport which doesn't satisfy the schema, in schema order.
+    fn failures<'s>(&'s self, passport: &Passport) -> Vec<(&'s str, FieldError)> {
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                field
+                    .check(passport)
+                    .err()
+                    .map(|err| (field.key.as_str(), err))
+            })
+            .collect()
+    }
+
+    fn has_required_fields(&self, passport: &Passport) -> bool {
+        self.fields
+            .iter()
+            .filter(|field| field.required)
+            .all(|field| passport.get(&field.key).is_some())
+    }
+
+    fn is_valid(&self, passport: &Passport) -> bool {
+        self.fields
+            .iter()
+            .all(|field| field.check(passport).is_ok())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Passport<'a> {
+    fields: HashMap<&'a str, &'a str>,
+}
+impl<'a> Passport<'a> {
+    fn get(&self, key: &str) -> Option<&'a str> {
+        self.fields.get(key).copied()
+    }
+
+    /// Set the value of `key`, returning the previous one.
+    fn insert(&mut self, key: &'a str, value: &'a str) -> Option<&'a str> {
+        self.fields.insert(key, value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum IssueKind<'a> {
+    /// A key which isn't part of the schema, the pair is ignored.
+    UnknownKey(&'a str),
+    /// A key which was already set for the passport, the later value wins.
+    DuplicateKey(&'a str),
+    /// A token which isn't a `key:value` pair, it is ignored.
+    Malformed(&'a str),
+}
+
+/// Problem found while parsing, located by its 1-based line and column.
+#[derive(Debug, PartialEq)]
+struct ParseIssue<'a> {
+    line: usize,
+    column: usize,
+    /// Index of the passport the issue belongs to.
+    passport: usize,
+    kind: IssueKind<'a>,
+}
+impl fmt::Display for ParseIssue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {} (passport {}): ",
+            self.line,
+            self.column,
+            self.passport + 1
+        )?;
+        match self.kind {
+            IssueKind::UnknownKey(key) => write!(f, "unknown key `{}`", key),
+            IssueKind::DuplicateKey(key) => write!(f, "duplicate key `{}`", key),
+            IssueKind::Malformed(token) => write!(f, "malformed pair `{}`", token),
+        }
+    }
+}
+
+struct Parsed<'a> {
+    passports: Vec<Passport<'a>>,
+    issues: Vec<ParseIssue<'a>>,
+}
+
+fn split_pair(pair: &str) -> Option<(&str, &str)> {
+    let mut parts = pair.split(':');
+    let key = parts.next()?;
+    let value = parts.next()?;
+    if parts.next().is_some() {
+        None
+    } else {
+        Some((key, value))
+    }
+}
+
+/// Parse every passport, recording problems instead of giving up on them.
+///
+/// Blocks which consist only of blank lines don't produce a passport.
+fn parse_lenient<'a>(inp: &'a str, schema: &Schema) -> Parsed<'a> {
+    let mut passports = Vec::new();
+    let mut issues = Vec::new();
     let mut passport = Passport::default();
-    for line in inp.lines().map(str::trim) {
-        if line.is_empty() {
-            passports.push(std::mem::take(&mut passport));
+    let mut has_tokens = false;
+    for (i, line) in inp.lines().enumerate() {
+        if line.trim().is_empty() {
+            if has_tokens {
+                passports.push(std::mem::take(&mut passport));
+                has_tokens = false;
+            }
             continue;
         }
 
-        for pair in line.split_whitespace() {
-            if !passport.set_from_pair(pair) {
-                return None;
+        for token in line.split_whitespace() {
+            has_tokens = true;
+            let mut issue = |kind| {
+                issues.push(ParseIssue {
+                    line: i + 1,
+                    column: token.as_ptr() as usize - line.as_ptr() as usize + 1,
+                    passport: passports.len(),
+                    kind,
+                });
+            };
+            match split_pair(token) {
+                Some((key, _)) if !schema.knows_key(key) => issue(IssueKind::UnknownKey(key)),
+                Some((key, value)) => {
+                    if passport.insert(key, value).is_some() {
+                        issue(IssueKind::DuplicateKey(key));
+                    }
+                }
+                None => issue(IssueKind::Malformed(token)),
             }
         }
     }
 
-    if !inp.is_empty() {
+    if has_tokens {
         passports.push(passport);
     }
 
-    Some(passports)
+    Parsed { passports, issues }
+}
+
+/// Parse the passports, failing on unknown keys and malformed pairs.
+///
+/// Duplicate keys are accepted, the later value wins.
+fn parse_input<'a>(inp: &'a str, schema: &Schema) -> Option<Vec<Passport<'a>>> {
+    let Parsed { passports, issues } = parse_lenient(inp, schema);
+    if issues
+        .iter()
+        .all(|issue| matches!(issue.kind, IssueKind::DuplicateKey(_)))
+    {
+        Some(passports)
+    } else {
+        None
+    }
 }
 
-fn first_part<'a>(passports: impl IntoIterator<Item = &'a Passport<'a>>) -> usize {
+fn first_part<'a>(passports: impl IntoIterator<Item = &'a Passport<'a>>, schema: &Schema) -> usize {
     passports
         .into_iter()
-        .filter(|p| p.has_required_fields())
+        .filter(|p| schema.has_required_fields(p))
         .count()
 }
 
-fn second_part<'a>(passports: impl IntoIterator<Item = &'a Passport<'a>>) -> usize {
-    passports.into_iter().filter(|p| p.is_valid()).count()
+fn second_part<'a>(
+    passports: impl IntoIterator<Item = &'a Passport<'a>>,
+    schema: &Schema,
+) -> usize {
+    passports.into_iter().filter(|p| schema.is_valid(p)).count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A passport as shown in the table on the page, with one cell per schema field.
+struct PassportRow {
+    cells: Vec<(Option<String>, Option<FieldError>)>,
+    valid: bool,
+}
+impl PassportRow {
+    fn new(schema: &Schema, passport: &Passport) -> Self {
+        let cells = schema
+            .fields
+            .iter()
+            .map(|field| {
+                let value = passport.get(&field.key).map(str::to_owned);
+                (value, field.check(passport).err())
+            })
+            .collect::<Vec<_>>();
+        let valid = cells.iter().all(|(_, err)| err.is_none());
+        Self { cells, valid }
+    }
+
+    fn render(&self, index: usize) -> Html {
+        let render_cell = |(value, err): &(Option<String>, Option<FieldError>)| {
+            let value = value.as_deref().unwrap_or_default();
+            if let Some(err) = err {
+                html! {
+                    <td class="failed" title=err.to_string()>
+                        { if value.is_empty() { err.to_string() } else { value.to_owned() } }
+                    </td>
+                }
+            } else {
+                html! { <td>{ value }</td> }
+            }
+        };
+        let class = if self.valid { "passed" } else { "failed" };
+        html! {
+            <tr>
+                <td class=class>{ index + 1 }</td>
+                { for self.cells.iter().map(render_cell) }
+            </tr>
+        }
+    }
+}
+
+pub enum Msg {
+    Solve,
+}
+
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    schema_area: NodeRef,
+    columns: Vec<String>,
+    rows: Vec<PassportRow>,
+    complete: usize,
+    /// Problems found while parsing the passports.
+    issues: Vec<String>,
+}
+impl Page {
+    fn solve(&mut self, raw_input: &str) -> Result<(), String> {
+        let schema_area = self.schema_area.cast::<HtmlTextAreaElement>().unwrap();
+        let schema = Schema::from_input(&schema_area.value());
+        if let Err(err) = &schema {
+            schema_area.set_custom_validity(&err.to_string());
+        } else {
+            schema_area.set_custom_validity("");
+        }
+        let schema = schema.map_err(|_| "invalid schema".to_owned())?;
+        let Parsed { passports, issues } = parse_lenient(raw_input, &schema);
+
+        self.columns = schema.fields.iter().map(|f| f.key.clone()).collect();
+        self.rows = passports
+            .iter()
+            .map(|passport| PassportRow::new(&schema, passport))
+            .collect();
+        self.complete = first_part(&passports, &schema);
+        self.issues = issues.iter().map(ToString::to_string).collect();
+        Ok(())
+    }
+
+    fn render_issues(&self) -> Html {
+        if self.issues.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <ul class="issues">
+                { for self.issues.iter().map(|issue| html! { <li>{ issue }</li> }) }
+            </ul>
+        }
+    }
 
-    const EXAMPLE_INPUT: &str = r#"
-        ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-        byr:1937 iyr:2017 cid:147 hgt:183cm
+    fn render_table(&self) -> Html {
+        if self.rows.is_empty() {
+            return html! {};
+        }
 
-        iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-        hcl:#cfa07d byr:1929
+        let valid = self.rows.iter().filter(|row| row.valid).count();
+        html! {
+            <>
+                <ul>
+                    <li>
+                        { format!("{} of {} with all required fields ", self.complete, self.rows.len()) }
+                        <CopyButton text=self.complete.to_string() />
+                    </li>
+                    <li>
+                        { format!("{} of {} valid ", valid, self.rows.len()) }
+                        <CopyButton text=valid.to_string() />
+                    </li>
+                </ul>
+                <table class="passports">
+                    <thead>
+                        <tr>
+                            <th>{ "#" }</th>
+                            { for self.columns.iter().map(|key| html! { <th>{ key }</th> }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for self.rows.iter().enumerate().map(|(i, row)| row.render(i)) }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
 
-        hcl:#ae17e1 iyr:2013
-        eyr:2024
-        ecl:brn pid:760753108 byr:1931
-        hgt:179cm
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            schema_area: NodeRef::default(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            complete: 0,
+            issues: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                match self.solve(&raw_input) {
+                    Ok(()) => {
+                        input_area.set_custom_validity("");
+                        results::record(4, &raw_input);
+                    }
+                    Err(err) => {
+                        self.rows.clear();
+                        self.issues.clear();
+                        input_area.set_custom_validity(&err);
+                    }
+                }
+
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            let schema_area = self.schema_area.cast::<HtmlTextAreaElement>().unwrap();
+            let schema = DEFAULT_SCHEMA
+                .trim()
+                .lines()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n");
+            schema_area.set_value(&schema);
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link,
+            input_area,
+            schema_area,
+            ..
+        } = &self;
+        html! {
+            <>
+                <textarea class="schema" ref=schema_area.clone() />
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=4 input=input_area.clone() />
+                <LoadExample day=4 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_issues() }
+                { self.render_table() }
+            </>
+        }
+    }
+}
 
-        hcl:#cfa07d eyr:2025 pid:166559648
-        iyr:2011 ecl:brn hgt:59in   
-    "#;
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let schema = Schema::get_default();
+    Some(first_part(&parse_input(input, schema)?, schema).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let schema = Schema::get_default();
+    Some(second_part(&parse_input(input, schema)?, schema).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+    byr:1937 iyr:2017 cid:147 hgt:183cm
+
+    iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+    hcl:#cfa07d byr:1929
+
+    hcl:#ae17e1 iyr:2013
+    eyr:2024
+    ecl:brn pid:760753108 byr:1931
+    hgt:179cm
+
+    hcl:#cfa07d eyr:2025 pid:166559648
+    iyr:2011 ecl:brn hgt:59in   
+"#;
+
+const VALID_PASSPORTS: &str = r#"
+    pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+    hcl:#623a2f
+    
+    eyr:2029 ecl:blu cid:129 byr:1989
+    iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+    
+    hcl:#888785
+    hgt:164cm byr:2001 iyr:2015 cid:88
+    pid:545766238 ecl:hzl
+    eyr:2022
+    
+    iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+"#;
+
+const INVALID_PASSPORTS: &str = r#"
+    eyr:1972 cid:100
+    hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+    
+    iyr:2019
+    hcl:#602927 eyr:1967 hgt:170cm
+    ecl:grn pid:012533040 byr:1946
+    
+    hcl:dab227 iyr:2012
+    ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+    
+    hgt:59cm ecl:zzz
+    eyr:2038 hcl:74454a iyr:2023
+    pid:3556412378 byr:2007
+"#;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: EXAMPLE_INPUT,
+        answers: &[Some("2"), None],
+    },
+    Example {
+        input: VALID_PASSPORTS,
+        answers: &[None, Some("4")],
+    },
+    Example {
+        input: INVALID_PASSPORTS,
+        answers: &[None, Some("0")],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_default(input: &str) -> Vec<Passport<'_>> {
+        parse_input(input, Schema::get_default()).expect("failed to parse input")
+    }
 
     #[test]
     fn first() {
-        let sol = first_part(&parse_input(EXAMPLE_INPUT).expect("failed to parse input"));
+        let sol = first_part(&parse_default(EXAMPLE_INPUT), Schema::get_default());
         assert_eq!(sol, 2);
     }
 
-    const VALID_PASSPORTS: &str = r#"
-        pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-        hcl:#623a2f
-        
-        eyr:2029 ecl:blu cid:129 byr:1989
-        iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
-        
-        hcl:#888785
-        hgt:164cm byr:2001 iyr:2015 cid:88
-        pid:545766238 ecl:hzl
-        eyr:2022
-        
-        iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
-    "#;
-    const INVALID_PASSPORTS: &str = r#"
-        eyr:1972 cid:100
-        hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
-        
-        iyr:2019
-        hcl:#602927 eyr:1967 hgt:170cm
-        ecl:grn pid:012533040 byr:1946
-        
-        hcl:dab227 iyr:2012
-        ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
-        
-        hgt:59cm ecl:zzz
-        eyr:2038 hcl:74454a iyr:2023
-        pid:3556412378 byr:2007
-    "#;
-
     #[test]
     fn second_valid() {
-        let sol = second_part(&parse_input(VALID_PASSPORTS).expect("failed to parse input"));
+        let sol = second_part(&parse_default(VALID_PASSPORTS), Schema::get_default());
         assert_eq!(sol, 4);
     }
 
     #[test]
     fn second_invalid() {
-        let sol = second_part(&parse_input(INVALID_PASSPORTS).expect("failed to parse input"));
+        let sol = second_part(&parse_default(INVALID_PASSPORTS), Schema::get_default());
         assert_eq!(sol, 0);
     }
+
+    #[test]
+    fn custom_schema() {
+        let schema = Schema::from_input(
+            r#"
+            // only the height matters, and it must be metric
+            hgt number 150-193cm
+            byr optional any
+            iyr optional any
+            eyr optional any
+            hcl optional any
+            ecl optional any
+            pid optional any
+            cid optional any
+            "#,
+        )
+        .expect("failed to parse schema");
+        let passports = parse_input(INVALID_PASSPORTS, &schema).expect("failed to parse input");
+        assert_eq!(first_part(&passports, &schema), 4);
+        assert_eq!(second_part(&passports, &schema), 2);
+
+        // keys unknown to the schema are rejected
+        let schema = Schema::from_input("byr number 1920-2002").expect("failed to parse schema");
+        assert!(parse_input(EXAMPLE_INPUT, &schema).is_none());
+    }
+
+    #[test]
+    fn schema_errors() {
+        let err = |s| Schema::from_input(s).expect_err("schema parsed");
+        assert_eq!(err("byr").message, "missing rule");
+        assert_eq!(err("byr optional").message, "missing rule");
+        assert_eq!(
+            err("byr number 1-a").message,
+            "expected ranges like `150-193cm`"
+        );
+        assert_eq!(err("hcl regex (").message, "invalid regular expression");
+        assert_eq!(err("pid length nine").message, "expected a length");
+        assert_eq!(err("pid digits nine").message, "expected a digit count");
+        assert_eq!(
+            err("byr number 2002-1920").message,
+            "range starts after it ends"
+        );
+        assert_eq!(err("ecl color red").message, "unknown rule");
+        assert_eq!(err("\nbyr any\nbyr any").line, 3);
+    }
+
+    #[test]
+    fn rules() {
+        let hgt = Rule::from_input("number", "150-193cm 59-76in").expect("failed to parse rule");
+        assert!(hgt.matches("150cm"));
+        assert!(hgt.matches("76in"));
+        assert!(!hgt.matches("194cm"));
+        assert!(!hgt.matches("170"));
+
+        let byr = Rule::from_input("number", "1920-2002").expect("failed to parse rule");
+        assert!(byr.matches("2002"));
+        assert!(!byr.matches("2003"));
+        assert!(!byr.matches("2000cm"));
+
+        let hcl = Rule::from_input("regex", "#[0-9a-f]{6}").expect("failed to parse rule");
+        assert!(hcl.matches("#123abc"));
+        assert!(!hcl.matches("#123abcd"));
+        assert!(!hcl.matches("x#123abc"));
+    }
+
+    #[test]
+    fn failures() {
+        let schema = Schema::get_default();
+        let passports = parse_default(INVALID_PASSPORTS);
+        let failures = passports
+            .iter()
+            .map(|passport| schema.failures(passport))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failures[0],
+            vec![
+                ("eyr", FieldError::OutOfRange),
+                ("hgt", FieldError::BadFormat),
+                ("pid", FieldError::NotNumeric),
+            ]
+        );
+        assert_eq!(failures[1], vec![("eyr", FieldError::OutOfRange)]);
+        assert_eq!(failures[2], vec![("hcl", FieldError::BadFormat)]);
+        assert_eq!(
+            failures[3],
+            vec![
+                ("byr", FieldError::OutOfRange),
+                ("iyr", FieldError::OutOfRange),
+                ("eyr", FieldError::OutOfRange),
+                ("hgt", FieldError::OutOfRange),
+                ("hcl", FieldError::BadFormat),
+                ("ecl", FieldError::BadFormat),
+                ("pid", FieldError::BadFormat),
+            ]
+        );
+
+        let passports = parse_default(EXAMPLE_INPUT);
+        assert_eq!(
+            schema.failures(&passports[1]),
+            vec![("hgt", FieldError::Missing)]
+        );
+    }
+
+    #[test]
+    fn pid_digits() {
+        let pid = Rule::from_input("digits", "9").expect("failed to parse rule");
+        assert_eq!(pid.check("000000001"), Ok(()));
+        assert_eq!(pid.check("0123456789"), Err(FieldError::BadFormat));
+        assert_eq!(pid.check("01234567a"), Err(FieldError::NotNumeric));
+        assert_eq!(pid.check("+12345678"), Err(FieldError::NotNumeric));
+    }
+
+    #[test]
+    fn lenient() {
+        let input = "
+            byr:1937 foo:bar
+            iyr:2017 byr:1938
+
+            pid:1:2 hgt
+            ecl:gry
+        ";
+        let parsed = parse_lenient(input, Schema::get_default());
+        assert_eq!(parsed.passports.len(), 2);
+        assert_eq!(parsed.passports[0].get("byr"), Some("1938"));
+        assert_eq!(parsed.passports[1].get("ecl"), Some("gry"));
+        assert_eq!(
+            parsed.issues,
+            vec![
+                ParseIssue {
+                    line: 2,
+                    column: 22,
+                    passport: 0,
+                    kind: IssueKind::UnknownKey("foo"),
+                },
+                ParseIssue {
+                    line: 3,
+                    column: 22,
+                    passport: 0,
+                    kind: IssueKind::DuplicateKey("byr"),
+                },
+                ParseIssue {
+                    line: 5,
+                    column: 13,
+                    passport: 1,
+                    kind: IssueKind::Malformed("pid:1:2"),
+                },
+                ParseIssue {
+                    line: 5,
+                    column: 21,
+                    passport: 1,
+                    kind: IssueKind::Malformed("hgt"),
+                },
+            ]
+        );
+        assert_eq!(
+            parsed.issues[0].to_string(),
+            "line 2, column 22 (passport 1): unknown key `foo`"
+        );
+        assert!(parse_input(input, Schema::get_default()).is_none());
+        assert!(parse_input("byr:1 byr:2", Schema::get_default()).is_some());
+    }
 }
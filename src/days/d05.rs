@@ -1,24 +1,159 @@
-type SeatId = u16;
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
+use std::fmt;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
 
-const ROW_BITS: SeatId = 7;
-const TOTAL_ROWS: SeatId = 1 << ROW_BITS;
+type SeatId = u32;
 
-const COL_BITS: SeatId = 3;
-const TOTAL_COLS: SeatId = 1 << COL_BITS;
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Seat {
+    row: u16,
+    col: u16,
+}
 
-fn parse_seat_id(s: &str) -> SeatId {
-    s.chars()
-        .map(|c| matches!(c, 'B' | 'R'))
-        .fold(0, |i, b| (i << 1) + b as SeatId)
+#[derive(Debug, PartialEq)]
+enum DecodeError {
+    Length {
+        expected: usize,
+        actual: usize,
+    },
+    /// A letter which isn't valid at its 1-based position.
+    InvalidLetter {
+        position: usize,
+        letter: char,
+    },
+    /// The code addresses a row or column which doesn't exist.
+    OutOfBounds,
+}
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { expected, actual } => {
+                write!(f, "expected {} letters, got {}", expected, actual)
+            }
+            Self::InvalidLetter { position, letter } => {
+                write!(f, "invalid letter {:?} at position {}", letter, position)
+            }
+            Self::OutOfBounds => f.write_str("seat doesn't exist"),
+        }
+    }
 }
 
-#[allow(clippy::cast_possible_truncation)]
-fn get_seat_pos(id: SeatId) -> (u8, u8) {
-    ((id >> COL_BITS) as u8, (id & (TOTAL_COLS - 1)) as u8)
+/// Number of binary partitioning steps needed to pick one of `n` items.
+fn partition_steps(n: u16) -> usize {
+    if n <= 1 {
+        0
+    } else {
+        (16 - (n - 1).leading_zeros()) as usize
+    }
 }
 
-fn parse_input(s: &str) -> Vec<SeatId> {
-    s.split_whitespace().map(parse_seat_id).collect()
+/// Layout of the seats in the plane.
+///
+/// Rows and columns are picked using binary space partitioning.
+/// If their number isn't a power of two, the codes for the missing rows and columns are invalid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Geometry {
+    rows: u16,
+    cols: u16,
+}
+impl Geometry {
+    /// The plane from the puzzle text.
+    const DEFAULT: Self = Self { rows: 128, cols: 8 };
+    /// Largest plane the page accepts, the seat map draws every seat.
+    const MAX_ROWS: u16 = 256;
+    const MAX_COLS: u16 = 16;
+
+    fn row_letters(self) -> usize {
+        partition_steps(self.rows)
+    }
+
+    fn col_letters(self) -> usize {
+        partition_steps(self.cols)
+    }
+
+    fn code_len(self) -> usize {
+        self.row_letters() + self.col_letters()
+    }
+
+    /// Decode a boarding pass like `FBFBBFFRLR`.
+    fn decode(self, code: &str) -> Result<Seat, DecodeError> {
+        let actual = code.chars().count();
+        if actual != self.code_len() {
+            return Err(DecodeError::Length {
+                expected: self.code_len(),
+                actual,
+            });
+        }
+
+        let (mut row, mut col) = (0, 0);
+        for (i, letter) in code.chars().enumerate() {
+            let (value, bit) = match (i < self.row_letters(), letter) {
+                (true, 'F') => (&mut row, 0),
+                (true, 'B') => (&mut row, 1),
+                (false, 'L') => (&mut col, 0),
+                (false, 'R') => (&mut col, 1),
+                _ => {
+                    return Err(DecodeError::InvalidLetter {
+                        position: i + 1,
+                        letter,
+                    })
+                }
+            };
+            *value = (*value << 1) | bit;
+        }
+
+        if row < self.rows && col < self.cols {
+            Ok(Seat { row, col })
+        } else {
+            Err(DecodeError::OutOfBounds)
+        }
+    }
+
+    /// Encode a seat as a boarding pass, `None` if the seat doesn't exist.
+    fn encode(self, seat: Seat) -> Option<String> {
+        if seat.row >= self.rows || seat.col >= self.cols {
+            return None;
+        }
+
+        let letters = |value: u16, len: usize, zero: char, one: char| {
+            (0..len)
+                .rev()
+                .map(move |bit| if value >> bit & 1 == 1 { one } else { zero })
+        };
+        Some(
+            letters(seat.row, self.row_letters(), 'F', 'B')
+                .chain(letters(seat.col, self.col_letters(), 'L', 'R'))
+                .collect(),
+        )
+    }
+
+    fn seat_id(self, seat: Seat) -> SeatId {
+        SeatId::from(seat.row) * SeatId::from(self.cols) + SeatId::from(seat.col)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn get_seat_pos(self, id: SeatId) -> Seat {
+        let cols = SeatId::from(self.cols);
+        Seat {
+            row: (id / cols) as u16,
+            col: (id % cols) as u16,
+        }
+    }
+
+    fn parse_seat_id(self, code: &str) -> Result<SeatId, DecodeError> {
+        self.decode(code).map(|seat| self.seat_id(seat))
+    }
+}
+
+fn parse_input(s: &str, geometry: Geometry) -> Result<Vec<SeatId>, DecodeError> {
+    s.split_whitespace()
+        .map(|code| geometry.parse_seat_id(code))
+        .collect()
 }
 
 fn first_part(seats: impl IntoIterator<Item = SeatId>) -> Option<SeatId> {
@@ -37,30 +172,381 @@ fn second_part(mut seats: Vec<SeatId>) -> Option<SeatId> {
     })
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SeatState {
+    Occupied,
+    Free,
+    /// Free seat in front of all occupied ones, which doesn't exist on this aircraft.
+    MissingFront,
+    /// Free seat behind all occupied ones, which doesn't exist on this aircraft.
+    MissingBack,
+    Yours,
+}
+impl SeatState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Occupied => "occupied",
+            Self::Free => "free",
+            Self::MissingFront => "missing-front",
+            Self::MissingBack => "missing-back",
+            Self::Yours => "yours",
+        }
+    }
+}
+
+/// State of every seat, indexed by row and then column.
+fn seat_map(geometry: Geometry, seats: &[SeatId]) -> Vec<Vec<SeatState>> {
+    let mut map =
+        vec![vec![SeatState::Free; usize::from(geometry.cols)]; usize::from(geometry.rows)];
+    for &id in seats {
+        let seat = geometry.get_seat_pos(id);
+        if let Some(state) = map
+            .get_mut(usize::from(seat.row))
+            .and_then(|row| row.get_mut(usize::from(seat.col)))
+        {
+            *state = SeatState::Occupied;
+        }
+    }
+
+    let front = seats.iter().min().copied().unwrap_or(0);
+    let back = seats.iter().max().copied().unwrap_or(0);
+    let yours = second_part(seats.to_vec());
+    for (row, states) in (0..).zip(&mut map) {
+        for (col, state) in (0..).zip(states) {
+            let id = geometry.seat_id(Seat { row, col });
+            if *state != SeatState::Free {
+                continue;
+            }
+            *state = if Some(id) == yours {
+                SeatState::Yours
+            } else if id < front {
+                SeatState::MissingFront
+            } else if id > back {
+                SeatState::MissingBack
+            } else {
+                SeatState::Free
+            };
+        }
+    }
+
+    map
+}
+
+pub enum Msg {
+    Solve,
+    SetSize,
+}
+
+/// Reads a plane dimension from `input`, marking it as invalid if it isn't in `1..=max`.
+fn read_dimension(input: &NodeRef, max: u16) -> Option<u16> {
+    let input = input.cast::<HtmlInputElement>().unwrap();
+    match input.value().parse() {
+        Ok(value) if (1..=max).contains(&value) => {
+            input.set_custom_validity("");
+            Some(value)
+        }
+        _ => {
+            input.set_custom_validity(&format!("must be between 1 and {}", max));
+            None
+        }
+    }
+}
+
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    rows_input: NodeRef,
+    cols_input: NodeRef,
+    /// `None` while the rows or columns are invalid.
+    geometry: Option<Geometry>,
+    /// Geometry the map was drawn for.
+    map_geometry: Geometry,
+    seats: Vec<SeatId>,
+    map: Vec<Vec<SeatState>>,
+}
+impl Page {
+    fn render_seat(&self, seat: Seat, state: SeatState) -> Html {
+        let geometry = self.map_geometry;
+        let title = format!(
+            "{} (id {})",
+            geometry.encode(seat).unwrap_or_default(),
+            geometry.seat_id(seat)
+        );
+        html! { <td class=state.as_str() title=title /> }
+    }
+
+    fn render_map(&self) -> Html {
+        if self.map.is_empty() {
+            return html! {};
+        }
+
+        let render_row = |(row, states): (u16, &Vec<SeatState>)| {
+            html! {
+                <tr>
+                    <th>{ row }</th>
+                    {
+                        for (0..).zip(states).map(|(col, &state)| {
+                            self.render_seat(Seat { row, col }, state)
+                        })
+                    }
+                </tr>
+            }
+        };
+        html! {
+            <table class="seat-map">
+                { for (0..).zip(&self.map).map(render_row) }
+            </table>
+        }
+    }
+
+    fn render_answers(&self) -> Html {
+        let render_answer = |label: &str, answer: Option<SeatId>| {
+            if let Some(id) = answer {
+                html! {
+                    <li>
+                        { format!("{}: ", label) }
+                        <code>{ id }</code>
+                        <CopyButton text=id.to_string() />
+                    </li>
+                }
+            } else {
+                html! {}
+            }
+        };
+        html! {
+            <ul>
+                { render_answer("highest seat id", first_part(self.seats.iter().copied())) }
+                { render_answer("your seat id", second_part(self.seats.clone())) }
+            </ul>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            rows_input: NodeRef::default(),
+            cols_input: NodeRef::default(),
+            geometry: Some(Geometry::DEFAULT),
+            map_geometry: Geometry::DEFAULT,
+            seats: Vec::new(),
+            map: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                let geometry = if let Some(geometry) = self.geometry {
+                    geometry
+                } else {
+                    self.seats.clear();
+                    self.map.clear();
+                    input_area.set_custom_validity("invalid plane size");
+                    return true;
+                };
+                match parse_input(&raw_input, geometry) {
+                    Ok(seats) => {
+                        self.map = seat_map(geometry, &seats);
+                        self.map_geometry = geometry;
+                        self.seats = seats;
+                        input_area.set_custom_validity("");
+                        results::record(5, &raw_input);
+                    }
+                    Err(err) => {
+                        self.seats.clear();
+                        self.map.clear();
+                        input_area.set_custom_validity(&err.to_string());
+                    }
+                }
+
+                true
+            }
+            Msg::SetSize => {
+                let rows = read_dimension(&self.rows_input, Geometry::MAX_ROWS);
+                let cols = read_dimension(&self.cols_input, Geometry::MAX_COLS);
+                self.geometry = rows.and_then(|rows| cols.map(|cols| Geometry { rows, cols }));
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link,
+            input_area,
+            rows_input,
+            cols_input,
+            ..
+        } = &self;
+        let Geometry { rows, cols } = Geometry::DEFAULT;
+        html! {
+            <>
+                <div>
+                    <input ref=rows_input.clone() type="number" min="1" max=Geometry::MAX_ROWS value=rows oninput=link.callback(|_| Msg::SetSize) />
+                    <span>{ " rows, " }</span>
+                    <input ref=cols_input.clone() type="number" min="1" max=Geometry::MAX_COLS value=cols oninput=link.callback(|_| Msg::SetSize) />
+                    <span>{ " columns" }</span>
+                </div>
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=5 input=input_area.clone() />
+                <LoadExample day=5 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_answers() }
+                { self.render_map() }
+            </>
+        }
+    }
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    first_part(parse_input(input, Geometry::DEFAULT).ok()?).map(|id| id.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(parse_input(input, Geometry::DEFAULT).ok()?).map(|id| id.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    BFFFBBFRRR
+    FFFBBBFRRR
+    BBFFBBFRLL
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("820"), None],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example_input() -> Vec<SeatId> {
+        parse_input(EXAMPLE_INPUT, Geometry::DEFAULT).expect("failed to parse input")
+    }
+
     #[test]
     fn find_index() {
-        assert_eq!(get_seat_pos(parse_seat_id("BFFFBBFRRR")), (70, 7));
-        assert_eq!(get_seat_pos(parse_seat_id("FFFBBBFRRR")), (14, 7));
-        assert_eq!(get_seat_pos(parse_seat_id("BBFFBBFRLL")), (102, 4));
+        let geometry = Geometry::DEFAULT;
+        let pos = |code| geometry.get_seat_pos(geometry.parse_seat_id(code).unwrap());
+        assert_eq!(pos("BFFFBBFRRR"), Seat { row: 70, col: 7 });
+        assert_eq!(pos("FFFBBBFRRR"), Seat { row: 14, col: 7 });
+        assert_eq!(pos("BBFFBBFRLL"), Seat { row: 102, col: 4 });
     }
 
-    const EXAMPLE_INPUT: &str = r#"
-        BFFFBBFRRR
-        FFFBBBFRRR
-        BBFFBBFRLL
-    "#;
-
     #[test]
     fn first() {
-        assert_eq!(first_part(parse_input(EXAMPLE_INPUT)), Some(820));
+        assert_eq!(first_part(example_input()), Some(820));
     }
 
     #[test]
     fn second() {
-        assert_eq!(second_part(parse_input(EXAMPLE_INPUT)), Some(120));
+        assert_eq!(second_part(example_input()), Some(120));
+    }
+
+    #[test]
+    fn strict_decoding() {
+        let geometry = Geometry::DEFAULT;
+        assert_eq!(
+            geometry.decode("FBFBBFFRL"),
+            Err(DecodeError::Length {
+                expected: 10,
+                actual: 9
+            })
+        );
+        assert_eq!(
+            geometry.decode("FBFBBFFRLX"),
+            Err(DecodeError::InvalidLetter {
+                position: 10,
+                letter: 'X'
+            })
+        );
+        // column letters in the row part
+        assert_eq!(
+            geometry.decode("FBFBBFRRLR"),
+            Err(DecodeError::InvalidLetter {
+                position: 7,
+                letter: 'R'
+            })
+        );
+        assert!(parse_input("FBFBBFFRLR\nFBFBBFFRL", geometry).is_err());
+    }
+
+    #[test]
+    fn encoding() {
+        let geometry = Geometry::DEFAULT;
+        assert_eq!(
+            geometry.encode(Seat { row: 44, col: 5 }).as_deref(),
+            Some("FBFBBFFRLR")
+        );
+        assert_eq!(geometry.encode(Seat { row: 128, col: 0 }), None);
+        for id in 0..1024 {
+            let seat = geometry.get_seat_pos(id);
+            let code = geometry.encode(seat).expect("failed to encode");
+            assert_eq!(geometry.parse_seat_id(&code), Ok(id));
+        }
+    }
+
+    #[test]
+    fn custom_geometry() {
+        let geometry = Geometry { rows: 100, cols: 6 };
+        assert_eq!(geometry.row_letters(), 7);
+        assert_eq!(geometry.col_letters(), 3);
+        assert_eq!(
+            geometry.decode("BBFFFBBRLR"),
+            Ok(Seat {
+                row: 100 - 1,
+                col: 5
+            })
+        );
+        assert_eq!(geometry.decode("BBFFBFBLLL"), Err(DecodeError::OutOfBounds));
+        assert_eq!(geometry.decode("FFFFFFFRRL"), Err(DecodeError::OutOfBounds));
+        assert_eq!(geometry.seat_id(Seat { row: 2, col: 3 }), 15);
+
+        for id in 0..600 {
+            let seat = geometry.get_seat_pos(id);
+            let code = geometry.encode(seat).expect("failed to encode");
+            assert_eq!(geometry.parse_seat_id(&code), Ok(id));
+        }
+
+        let geometry = Geometry { rows: 1, cols: 1 };
+        assert_eq!(geometry.decode(""), Ok(Seat { row: 0, col: 0 }));
+    }
+
+    #[test]
+    fn map() {
+        let geometry = Geometry { rows: 3, cols: 2 };
+        let map = seat_map(geometry, &[2, 3, 5]);
+        assert_eq!(
+            map,
+            vec![
+                vec![SeatState::MissingFront, SeatState::MissingFront],
+                vec![SeatState::Occupied, SeatState::Occupied],
+                vec![SeatState::Yours, SeatState::Occupied],
+            ]
+        );
+
+        let map = seat_map(geometry, &[1, 2]);
+        assert_eq!(map[0], vec![SeatState::MissingFront, SeatState::Occupied]);
+        assert_eq!(map[2], vec![SeatState::MissingBack, SeatState::MissingBack]);
     }
 }
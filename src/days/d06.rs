@@ -1,84 +1,509 @@
-use std::collections::HashSet;
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::{BitAnd, BitOr, BitXor},
+};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
 
-type Answers = HashSet<char>;
+/// Set of questions `a` to `z` stored as one bit per question.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Answers(u32);
+impl Answers {
+    const ALL: Self = Self((1 << 26) - 1);
 
-fn parse_answers(s: &str) -> Answers {
-    s.chars().collect()
+    fn bit(question: char) -> Option<u32> {
+        if question.is_ascii_lowercase() {
+            Some(1 << (question as u32 - 'a' as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Parse the questions answered by one person.
+    ///
+    /// On failure the 1-based column and the offending character are returned.
+    fn from_input(s: &str) -> Result<Self, (usize, char)> {
+        let mut answers = Self::default();
+        for (i, c) in s.chars().enumerate() {
+            if !answers.insert(c) {
+                return Err((i + 1, c));
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Add the question, returns `false` if it isn't one.
+    fn insert(&mut self, question: char) -> bool {
+        match Self::bit(question) {
+            Some(bit) => {
+                self.0 |= bit;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn contains(self, question: char) -> bool {
+        Self::bit(question).map_or(false, |bit| self.0 & bit != 0)
+    }
+
+    fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The questions in alphabetical order.
+    fn iter(self) -> impl Iterator<Item = char> {
+        ('a'..='z').filter(move |&q| self.contains(q))
+    }
+}
+impl BitOr for Answers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+impl BitAnd for Answers {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+impl BitXor for Answers {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct InvalidQuestion {
+    line: usize,
+    column: usize,
+    found: char,
 }
+impl fmt::Display for InvalidQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?} isn't a question, expected a to z",
+            self.line, self.column, self.found
+        )
+    }
+}
+
+/// Answers of every member of a group.
+#[derive(Debug, Default)]
+struct Group {
+    members: Vec<Answers>,
+}
+impl Group {
+    /// How many members answered each question with "yes".
+    fn histogram(&self) -> BTreeMap<char, usize> {
+        let mut histogram = BTreeMap::new();
+        for question in self.members.iter().flat_map(|answers| answers.iter()) {
+            *histogram.entry(question).or_default() += 1;
+        }
+        histogram
+    }
+
+    fn answered_by(&self, pred: impl Fn(usize) -> bool) -> Answers {
+        let mut answers = Answers::default();
+        for (question, count) in self.histogram() {
+            if pred(count) {
+                answers.insert(question);
+            }
+        }
+        answers
+    }
+
+    fn answered_by_at_least(&self, k: usize) -> Answers {
+        self.answered_by(|count| count >= k)
+    }
 
-type Group = Vec<Answers>;
+    fn answered_by_exactly_one(&self) -> Answers {
+        self.answered_by(|count| count == 1)
+    }
+
+    /// Questions answered by an odd number of members.
+    ///
+    /// This is the symmetric difference of all members' answers.
+    fn symmetric_difference(&self) -> Answers {
+        self.members
+            .iter()
+            .fold(Answers::default(), |acc, &answers| acc ^ answers)
+    }
+
+    fn union(&self) -> Answers {
+        self.members
+            .iter()
+            .fold(Answers::default(), |acc, &answers| acc | answers)
+    }
+
+    fn intersection(&self) -> Answers {
+        if self.members.is_empty() {
+            return Answers::default();
+        }
+        self.members
+            .iter()
+            .fold(Answers::ALL, |acc, &answers| acc & answers)
+    }
+
+    fn select(&self, query: Query) -> Answers {
+        match query {
+            Query::Anyone => self.union(),
+            Query::Everyone => self.intersection(),
+            Query::AtLeast(k) => self.answered_by_at_least(k),
+            Query::ExactlyOne => self.answered_by_exactly_one(),
+            Query::SymmetricDifference => self.symmetric_difference(),
+        }
+    }
+}
+
+/// Selects questions of a group based on how its members answered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Query {
+    Anyone,
+    Everyone,
+    AtLeast(usize),
+    ExactlyOne,
+    SymmetricDifference,
+}
+impl Query {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Anyone => "anyone",
+            Self::Everyone => "everyone",
+            Self::AtLeast(_) => "at least k",
+            Self::ExactlyOne => "exactly one",
+            Self::SymmetricDifference => "odd number (symmetric difference)",
+        }
+    }
+}
 
-fn parse_input(s: &str) -> Vec<Group> {
+fn parse_input(s: &str) -> Result<Vec<Group>, InvalidQuestion> {
     let mut groups = Vec::default();
-    let mut group = Group::new();
-    for line in s.trim().lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            groups.push(std::mem::take(&mut group));
+    let mut group = Group::default();
+    for (i, line) in s.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if !group.members.is_empty() {
+                groups.push(std::mem::take(&mut group));
+            }
             continue;
         }
 
-        group.push(parse_answers(line))
+        let answers = Answers::from_input(trimmed).map_err(|(column, found)| {
+            let indent = line.len() - line.trim_start().len();
+            InvalidQuestion {
+                line: i + 1,
+                column: indent + column,
+                found,
+            }
+        })?;
+        group.members.push(answers);
     }
 
-    if !group.is_empty() {
+    if !group.members.is_empty() {
         groups.push(group);
     }
 
-    groups
+    Ok(groups)
+}
+
+/// Sum of the number of questions selected by `query` for every group.
+fn count_selected(groups: &[Group], query: Query) -> usize {
+    groups.iter().map(|g| g.select(query).len()).sum()
+}
+
+fn first_part(groups: &[Group]) -> usize {
+    count_selected(groups, Query::Anyone)
+}
+
+fn second_part(groups: &[Group]) -> usize {
+    count_selected(groups, Query::Everyone)
+}
+
+pub enum Msg {
+    Solve,
+    SetQuery(Query),
+    SetK(InputData),
+}
+
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    query: Query,
+    /// Threshold used for [`Query::AtLeast`].
+    k: usize,
+    groups: Vec<Group>,
+}
+impl Page {
+    fn render_query(&self, query: Query) -> Html {
+        let checked = std::mem::discriminant(&self.query) == std::mem::discriminant(&query);
+        html! {
+            <label>
+                <input type="radio" name="query" checked=checked onclick=self.link.callback(move |_| Msg::SetQuery(query)) />
+                <span>{ query.label() }</span>
+            </label>
+        }
+    }
+
+    fn render_group(&self, (i, group): (usize, &Group)) -> Html {
+        let histogram = group
+            .histogram()
+            .into_iter()
+            .map(|(question, count)| format!("{}:{}", question, count))
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! {
+            <tr>
+                <td>{ i + 1 }</td>
+                <td>{ group.members.len() }</td>
+                <td><code>{ group.select(self.query).iter().collect::<String>() }</code></td>
+                <td>{ histogram }</td>
+            </tr>
+        }
+    }
+
+    fn render_groups(&self) -> Html {
+        if self.groups.is_empty() {
+            return html! {};
+        }
+
+        let total = count_selected(&self.groups, self.query);
+        html! {
+            <>
+                <p>
+                    { "sum of counts: " }
+                    <code>{ total }</code>
+                    <CopyButton text=total.to_string() />
+                </p>
+                <table class="groups">
+                    <thead>
+                        <tr>
+                            <th>{ "group" }</th>
+                            <th>{ "members" }</th>
+                            <th>{ "selected" }</th>
+                            <th>{ "answers per question" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for self.groups.iter().enumerate().map(|group| self.render_group(group)) }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            query: Query::Anyone,
+            k: 2,
+            groups: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                match parse_input(&raw_input) {
+                    Ok(groups) => {
+                        self.groups = groups;
+                        input_area.set_custom_validity("");
+                        results::record(6, &raw_input);
+                    }
+                    Err(err) => {
+                        self.groups.clear();
+                        input_area.set_custom_validity(&err.to_string());
+                    }
+                }
+
+                true
+            }
+            Msg::SetQuery(query) => {
+                self.query = match query {
+                    Query::AtLeast(_) => Query::AtLeast(self.k),
+                    query => query,
+                };
+                true
+            }
+            Msg::SetK(data) => {
+                if let Ok(k) = data.value.parse() {
+                    self.k = k;
+                    if let Query::AtLeast(_) = self.query {
+                        self.query = Query::AtLeast(k);
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link,
+            input_area,
+            k,
+            ..
+        } = &self;
+        let queries = [
+            Query::Anyone,
+            Query::Everyone,
+            Query::AtLeast(*k),
+            Query::ExactlyOne,
+            Query::SymmetricDifference,
+        ];
+        html! {
+            <>
+                <div>
+                    <span>{ "questions answered by " }</span>
+                    { for queries.iter().map(|&query| self.render_query(query)) }
+                    <span>{ "k = " }</span>
+                    <input type="number" min="1" value=k oninput=link.callback(Msg::SetK) />
+                </div>
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=6 input=input_area.clone() />
+                <LoadExample day=6 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_groups() }
+            </>
+        }
+    }
 }
 
-fn first_part(groups: Vec<Group>) -> usize {
-    groups
-        .into_iter()
-        .map(|g| g.into_iter().flatten().collect::<HashSet<_>>().len())
-        .sum()
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&parse_input(input).ok()?).to_string())
 }
 
-fn second_part(groups: Vec<Group>) -> usize {
-    groups
-        .into_iter()
-        .map(|g| {
-            let mut it = g.into_iter();
-            let intr = match it.next() {
-                Some(v) => v,
-                None => return 0,
-            };
-            it.fold(intr, |intr, a| intr.intersection(&a).copied().collect())
-                .len()
-        })
-        .sum()
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(&parse_input(input).ok()?).to_string())
 }
 
+const EXAMPLE_INPUT: &str = r#"
+    abc
+
+    a
+    b
+    c
+    
+    ab
+    ac
+    
+    a
+    a
+    a
+    a
+    
+    b
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("11"), Some("6")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        abc
-
-        a
-        b
-        c
-        
-        ab
-        ac
-        
-        a
-        a
-        a
-        a
-        
-        b
-    "#;
+    fn example_input() -> Vec<Group> {
+        parse_input(EXAMPLE_INPUT).expect("failed to parse input")
+    }
 
     #[test]
     fn first() {
-        assert_eq!(first_part(parse_input(EXAMPLE_INPUT)), 11);
+        assert_eq!(first_part(&example_input()), 11);
     }
 
     #[test]
     fn second() {
-        assert_eq!(second_part(parse_input(EXAMPLE_INPUT)), 6);
+        assert_eq!(second_part(&example_input()), 6);
+    }
+
+    #[test]
+    fn queries() {
+        let groups = example_input();
+        assert_eq!(count_selected(&groups, Query::AtLeast(1)), 11);
+        assert_eq!(count_selected(&groups, Query::AtLeast(2)), 2);
+        assert_eq!(count_selected(&groups, Query::ExactlyOne), 9);
+        assert_eq!(count_selected(&groups, Query::SymmetricDifference), 9);
+
+        let group = &parse_input("ab\nbc\nbcd").expect("failed to parse input")[0];
+        let set = |s| Answers::from_input(s).expect("failed to parse answers");
+        assert_eq!(group.answered_by_at_least(2), set("bc"));
+        assert_eq!(group.answered_by_exactly_one(), set("ad"));
+        assert_eq!(group.symmetric_difference(), set("abd"));
+        assert_eq!(
+            group.histogram().into_iter().collect::<Vec<_>>(),
+            vec![('a', 1), ('b', 3), ('c', 2), ('d', 1)]
+        );
+    }
+
+    #[test]
+    fn bitset() {
+        let answers = Answers::from_input("zab").expect("failed to parse answers");
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.iter().collect::<String>(), "abz");
+        assert!(answers.contains('z'));
+        assert!(!answers.contains('c'));
+        assert!(!answers.contains('A'));
+
+        let other = Answers::from_input("bc").expect("failed to parse answers");
+        assert_eq!((answers | other).iter().collect::<String>(), "abcz");
+        assert_eq!((answers & other).iter().collect::<String>(), "b");
+        assert_eq!((answers ^ other).iter().collect::<String>(), "acz");
+        assert!((answers & Answers::default()).is_empty());
+    }
+
+    #[test]
+    fn invalid_questions() {
+        assert_eq!(Answers::from_input("abC"), Err((3, 'C')));
+        assert_eq!(
+            parse_input("ab\n\n  a1").err(),
+            Some(InvalidQuestion {
+                line: 3,
+                column: 4,
+                found: '1'
+            })
+        );
+        assert_eq!(
+            parse_input("ab\n\n  a1").unwrap_err().to_string(),
+            "line 3, column 4: '1' isn't a question, expected a to z"
+        );
     }
 }
@@ -1,17 +1,28 @@
-use std::collections::HashMap;
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{download, results, share},
+    utils::ResultExt,
+};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt::{self, Write},
+};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
 
 const COLOR_SHINY_GOLD: &str = "shiny gold";
 
-struct BagContents<'a> {
+struct BagContents {
     count: usize,
-    color: &'a str,
+    color: String,
 }
-impl<'a> BagContents<'a> {
-    fn from_input(s: &'a str) -> Option<Self> {
+impl BagContents {
+    fn from_input(s: &str) -> Option<Self> {
         let s = s.strip_suffix(" bags").or_else(|| s.strip_suffix(" bag"))?;
         let mut it = s.splitn(2, ' ');
         let count = it.next()?.parse().ok()?;
-        let color = it.next()?;
+        let color = it.next()?.to_owned();
 
         Some(Self { count, color })
     }
@@ -33,95 +44,918 @@ fn parse_bag(s: &str) -> Option<(&str, Vec<BagContents>)> {
     Some((color, contents))
 }
 
-fn parse_input(inp: &str) -> Option<HashMap<&str, Vec<BagContents>>> {
-    inp.trim()
-        .lines()
-        .map(|line| parse_bag(line.trim()))
-        .collect()
-}
-
-fn first_part<'a>(bags: &HashMap<&'a str, Vec<BagContents<'a>>>) -> usize {
-    fn contains_shiny_gold<'a>(
-        bags: &HashMap<&'a str, Vec<BagContents<'a>>>,
-        contains_map: &mut HashMap<&'a str, bool>,
-        color: &'a str,
-    ) -> bool {
-        contains_map.get(color).copied().unwrap_or_else(|| {
-            let contains = bags.get(color).map_or(false, |contents| {
-                contents.iter().any(|content| {
-                    content.color == COLOR_SHINY_GOLD
-                        || contains_shiny_gold(bags, contains_map, &content.color)
-                })
+/// Parses the rules, skipping blank lines.
+fn parse_input(inp: &str) -> Result<HashMap<String, Vec<BagContents>>, GraphError> {
+    let mut bags = HashMap::new();
+    for (i, line) in inp.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (color, contents) = parse_bag(line).ok_or(GraphError::InvalidRule { line: i + 1 })?;
+        if bags.insert(color.to_owned(), contents).is_some() {
+            return Err(GraphError::Duplicate {
+                line: i + 1,
+                color: color.to_owned(),
             });
+        }
+    }
+    Ok(bags)
+}
+
+#[derive(Debug, PartialEq)]
+enum GraphError {
+    InvalidRule {
+        line: usize,
+    },
+    /// A second rule for the same color.
+    Duplicate {
+        line: usize,
+        color: String,
+    },
+    /// Colors which are contained in other bags but have no rule of their own.
+    Undefined(Vec<String>),
+    /// Colors forming a containment cycle, with the first color repeated at the end.
+    Cycle(Vec<String>),
+    /// A bag which contains more bags than fit in a `usize`.
+    Overflow(String),
+}
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidRule { line } => write!(f, "line {}: invalid bag rule", line),
+            Self::Duplicate { line, color } => {
+                write!(f, "line {}: {} bags already have a rule", line, color)
+            }
+            Self::Undefined(colors) => write!(f, "no rules for: {}", colors.join(", ")),
+            Self::Cycle(colors) => write!(f, "bags contain themselves: {}", colors.join(" → ")),
+            Self::Overflow(color) => write!(f, "{} bags contain too many bags to count", color),
+        }
+    }
+}
+
+/// Orders the colors so that each bag comes after all the bags it directly contains.
+///
+/// Every contained color must have a rule and `containers` must be sorted.
+/// Fails with a containment cycle if there is one.
+fn contained_first<'a>(
+    contents: &'a HashMap<String, Vec<BagContents>>,
+    containers: &'a HashMap<String, Vec<String>>,
+) -> Result<Vec<&'a str>, Vec<&'a str>> {
+    let mut pending = contents
+        .iter()
+        .map(|(color, inner)| (color.as_str(), inner.len()))
+        .collect::<HashMap<_, _>>();
+    let mut ready = pending
+        .iter()
+        .filter(|(_, &count)| count == 0)
+        .map(|(&color, _)| color)
+        .collect::<Vec<_>>();
+    ready.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut order = Vec::with_capacity(contents.len());
+    while let Some(color) = ready.pop() {
+        order.push(color);
+        for container in containers.get(color).into_iter().flatten() {
+            if let Some(count) = pending.get_mut(container.as_str()) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(container);
+                }
+            }
+        }
+    }
+    if order.len() == contents.len() {
+        return Ok(order);
+    }
+
+    // every bag left over still contains a bag which is left over,
+    // so following those bags eventually runs into a cycle
+    let unordered = |color: &&str| pending[color] > 0;
+    let mut color = pending.keys().copied().filter(unordered).min().unwrap();
+    let mut path = Vec::new();
+    let mut seen = HashMap::new();
+    while !seen.contains_key(color) {
+        seen.insert(color, path.len());
+        path.push(color);
+        color = contents[color]
+            .iter()
+            .map(|content| content.color.as_str())
+            .find(unordered)
+            .unwrap();
+    }
+    let mut cycle = path.split_off(seen[color]);
+    cycle.push(color);
+    Err(cycle)
+}
+
+/// The bag rules as a graph, with an edge from each bag to the bags it directly contains.
+struct BagGraph {
+    contents: HashMap<String, Vec<BagContents>>,
+    /// Reversed edges: the bags which directly contain a bag.
+    containers: HashMap<String, Vec<String>>,
+    /// Every color, each after all the bags it directly contains.
+    order: Vec<String>,
+    /// Number of bags inside each bag.
+    totals: HashMap<String, usize>,
+}
+impl BagGraph {
+    /// Builds the graph after making sure every color has a rule, no bag contains itself
+    /// and the number of bags inside each bag can be counted.
+    fn new(contents: HashMap<String, Vec<BagContents>>) -> Result<Self, GraphError> {
+        let undefined = contents
+            .values()
+            .flatten()
+            .map(|content| &content.color)
+            .filter(|&color| !contents.contains_key(color))
+            .collect::<BTreeSet<_>>();
+        if !undefined.is_empty() {
+            return Err(GraphError::Undefined(
+                undefined.into_iter().cloned().collect(),
+            ));
+        }
+
+        let mut containers: HashMap<_, Vec<_>> = HashMap::new();
+        for (color, inner) in &contents {
+            for content in inner {
+                containers
+                    .entry(content.color.clone())
+                    .or_default()
+                    .push(color.clone());
+            }
+        }
+        for outer in containers.values_mut() {
+            outer.sort_unstable();
+        }
+        let order = contained_first(&contents, &containers)
+            .map(|order| order.into_iter().map(str::to_owned).collect::<Vec<_>>())
+            .map_err(|cycle| GraphError::Cycle(cycle.into_iter().map(str::to_owned).collect()))?;
 
-            contains_map.insert(color, contains);
-            contains
+        let mut totals = HashMap::with_capacity(order.len());
+        for color in &order {
+            let total = contents[color]
+                .iter()
+                .try_fold(0_usize, |total, content| {
+                    let inner: usize = totals[&content.color];
+                    content
+                        .count
+                        .checked_mul(inner.checked_add(1)?)?
+                        .checked_add(total)
+                })
+                .ok_or_else(|| GraphError::Overflow(color.clone()))?;
+            totals.insert(color.clone(), total);
+        }
+
+        Ok(Self {
+            contents,
+            containers,
+            order,
+            totals,
         })
     }
 
-    let mut contains_map = HashMap::new();
-    bags.keys()
-        .filter(|color| contains_shiny_gold(bags, &mut contains_map, color))
-        .count()
+    fn from_input(inp: &str) -> Result<Self, GraphError> {
+        Self::new(parse_input(inp)?)
+    }
+
+    /// Every color with a rule, in alphabetical order.
+    fn colors(&self) -> Vec<&str> {
+        let mut colors = self.contents.keys().map(String::as_str).collect::<Vec<_>>();
+        colors.sort_unstable();
+        colors
+    }
+
+    /// Bags which eventually contain at least one `color` bag.
+    fn containers_of(&self, color: &str) -> BTreeSet<&str> {
+        let mut found = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(color);
+        while let Some(color) = queue.pop_front() {
+            for container in self.containers.get(color).into_iter().flatten() {
+                if found.insert(container.as_str()) {
+                    queue.push_back(container);
+                }
+            }
+        }
+        found
+    }
+
+    /// Number of bags a `color` bag has to contain.
+    fn count_inside(&self, color: &str) -> usize {
+        self.totals.get(color).copied().unwrap_or(0)
+    }
+
+    /// Shortest chain of bags from `from` to `to` where each bag directly contains the next one.
+    ///
+    /// The path starts with `from` and ends with `to`.
+    fn path(&self, from: &str, to: &str) -> Option<Vec<&str>> {
+        let (from, _) = self.contents.get_key_value(from)?;
+        let from = from.as_str();
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(color) = queue.pop_front() {
+            if color == to {
+                let mut path = vec![color];
+                let mut color = color;
+                while let Some(&prev) = previous.get(color) {
+                    path.push(prev);
+                    color = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for content in self.contents.get(color).into_iter().flatten() {
+                let next = content.color.as_str();
+                if next != from && !previous.contains_key(next) {
+                    previous.insert(next, color);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Splits the bags into layers where each bag is placed below all bags containing it.
+    ///
+    /// Bags which aren't contained in any other bag make up the first layer.
+    /// Each further layer is ordered by the average position of the bags containing them,
+    /// which keeps the number of crossing edges down.
+    fn layers(&self) -> Vec<Vec<&str>> {
+        let mut depths = HashMap::with_capacity(self.contents.len());
+        for color in self.order.iter().rev() {
+            let depth = self
+                .containers
+                .get(color)
+                .into_iter()
+                .flatten()
+                .map(|container| depths[container.as_str()] + 1)
+                .max()
+                .unwrap_or(0);
+            depths.insert(color.as_str(), depth);
+        }
+
+        let mut layers: Vec<Vec<&str>> = Vec::new();
+        for color in self.colors() {
+            let depth = depths[color];
+            if layers.len() <= depth {
+                layers.resize_with(depth + 1, Vec::new);
+            }
+            layers[depth].push(color);
+        }
+
+        let mut positions = HashMap::with_capacity(self.contents.len());
+        for layer in &mut layers {
+            let mut keyed = layer
+                .iter()
+                .map(|&color| {
+                    let above = self
+                        .containers
+                        .get(color)
+                        .into_iter()
+                        .flatten()
+                        .map(|container| positions[container.as_str()])
+                        .collect::<Vec<usize>>();
+                    #[allow(clippy::cast_precision_loss)]
+                    let key = if above.is_empty() {
+                        0.0
+                    } else {
+                        above.iter().sum::<usize>() as f64 / above.len() as f64
+                    };
+                    (key, color)
+                })
+                .collect::<Vec<_>>();
+            keyed.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            *layer = keyed.into_iter().map(|(_, color)| color).collect();
+            for (i, &color) in layer.iter().enumerate() {
+                positions.insert(color, i);
+            }
+        }
+        layers
+    }
+
+    /// Number of `inner` bags `outer` directly contains.
+    fn direct_count(&self, outer: &str, inner: &str) -> usize {
+        self.contents
+            .get(outer)
+            .and_then(|contents| contents.iter().find(|content| content.color == inner))
+            .map_or(0, |content| content.count)
+    }
+}
+
+/// Colors which a `color` bag contains, directly or indirectly, including `color` itself.
+fn reachable_from<'a>(
+    bags: &'a HashMap<String, Vec<BagContents>>,
+    color: &'a str,
+) -> BTreeSet<&'a str> {
+    let mut found = BTreeSet::new();
+    found.insert(color);
+    let mut queue = VecDeque::new();
+    queue.push_back(color);
+    while let Some(color) = queue.pop_front() {
+        for content in bags.get(color).into_iter().flatten() {
+            if found.insert(&content.color) {
+                queue.push_back(&content.color);
+            }
+        }
+    }
+    found
+}
+
+fn dot_id(color: &str) -> String {
+    format!("\"{}\"", color.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Describes the rules in the DOT language with an edge from each bag to the bags it contains.
+///
+/// If `highlight` is given, the bags inside it (and the bag itself) are drawn in red.
+fn to_dot(bags: &HashMap<String, Vec<BagContents>>, highlight: Option<&str>) -> String {
+    const HIGHLIGHT: &str = "color=red, fontcolor=red";
+
+    let highlighted = highlight
+        .map(|color| reachable_from(bags, color))
+        .unwrap_or_default();
+    let mut colors = bags.keys().map(String::as_str).collect::<Vec<_>>();
+    colors.sort_unstable();
+
+    let mut dot = String::from("digraph bags {\n");
+    for &color in &colors {
+        if highlighted.contains(color) {
+            writeln!(dot, "    {} [{}];", dot_id(color), HIGHLIGHT).unwrap();
+        } else {
+            writeln!(dot, "    {};", dot_id(color)).unwrap();
+        }
+    }
+    for &color in &colors {
+        for content in &bags[color] {
+            let mut attributes = format!("label={}", content.count);
+            if highlighted.contains(color) {
+                attributes.push_str(", ");
+                attributes.push_str(HIGHLIGHT);
+            }
+            writeln!(
+                dot,
+                "    {} -> {} [{}];",
+                dot_id(color),
+                dot_id(&content.color),
+                attributes
+            )
+            .unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn first_part(graph: &BagGraph) -> usize {
+    graph.containers_of(COLOR_SHINY_GOLD).len()
+}
+
+fn second_part(graph: &BagGraph) -> usize {
+    graph.count_inside(COLOR_SHINY_GOLD)
+}
+
+pub enum Msg {
+    Solve,
+    SetTarget(ChangeData),
+    SetPathEnd(ChangeData),
+    ToggleHighlight,
+    DownloadDot,
+}
+
+fn selected_value(data: ChangeData) -> Option<String> {
+    match data {
+        ChangeData::Select(select) => Some(select.value()),
+        _ => None,
+    }
+}
+
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    /// Graph of the last input which was successfully parsed.
+    graph: Option<BagGraph>,
+    target: String,
+    /// Color the path from `target` should lead to.
+    path_end: String,
+    /// Whether to highlight the bags inside `target` in the graph.
+    highlight: bool,
 }
+impl Page {
+    /// Keeps the selected colors when they still exist in the new rules.
+    fn reset_selection(&mut self) {
+        let colors = match &self.graph {
+            Some(graph) => graph.colors(),
+            None => return,
+        };
+        let pick = |current: &str| {
+            if colors.contains(&current) {
+                current.to_owned()
+            } else if colors.contains(&COLOR_SHINY_GOLD) {
+                COLOR_SHINY_GOLD.to_owned()
+            } else {
+                colors.first().copied().unwrap_or_default().to_owned()
+            }
+        };
+        self.target = pick(&self.target);
+        self.path_end = pick(&self.path_end);
+    }
+
+    fn render_picker(colors: &[&str], selected: &str, onchange: Callback<ChangeData>) -> Html {
+        html! {
+            <select onchange=onchange>
+                { for colors.iter().map(|&color| html! {
+                    <option value=color selected=color == selected>{ color }</option>
+                }) }
+            </select>
+        }
+    }
+
+    fn render_path(graph: &BagGraph, from: &str, to: &str) -> Html {
+        let path = match graph.path(from, to) {
+            Some(path) => path,
+            None => return html! { <p>{ format!("{} bags can't contain {} bags", from, to) }</p> },
+        };
+
+        let steps = path
+            .windows(2)
+            .map(|pair| format!("{} {}", graph.direct_count(pair[0], pair[1]), pair[1]))
+            .collect::<Vec<_>>();
+        let text = std::iter::once(path[0].to_owned())
+            .chain(steps)
+            .collect::<Vec<_>>()
+            .join(" → ");
+        html! {
+            <p>
+                { "path: " }
+                <code>{ &text }</code>
+                <CopyButton text=text.clone() />
+            </p>
+        }
+    }
 
-fn second_part<'a>(bags: &HashMap<&'a str, Vec<BagContents<'a>>>) -> usize {
-    fn count_bags_recursive<'a>(
-        bags: &HashMap<&'a str, Vec<BagContents<'a>>>,
-        totals: &mut HashMap<&'a str, usize>,
-        color: &'a str,
-    ) -> usize {
-        totals.get(color).copied().unwrap_or_else(|| {
-            let total = bags.get(color).map_or(0, |contents| {
+    fn render_queries(&self) -> Html {
+        let graph = match &self.graph {
+            Some(graph) => graph,
+            None => return html! {},
+        };
+        let colors = graph.colors();
+        if colors.is_empty() {
+            return html! {};
+        }
+
+        let target = self.target.as_str();
+        let containers = graph.containers_of(target);
+        let inside = graph.count_inside(target);
+        html! {
+            <>
+                <div>
+                    <span>{ "target color " }</span>
+                    { Self::render_picker(&colors, target, self.link.callback(Msg::SetTarget)) }
+                </div>
+                <p>
+                    { format!("bags which eventually contain {}: ", target) }
+                    <code>{ containers.len() }</code>
+                    <CopyButton text=containers.len().to_string() />
+                </p>
+                <p>{ containers.iter().copied().collect::<Vec<_>>().join(", ") }</p>
+                <p>
+                    { format!("bags inside one {} bag: ", target) }
+                    <code>{ inside }</code>
+                    <CopyButton text=inside.to_string() />
+                </p>
+                <div>
+                    <span>{ format!("path from {} to ", target) }</span>
+                    { Self::render_picker(&colors, &self.path_end, self.link.callback(Msg::SetPathEnd)) }
+                </div>
+                { Self::render_path(graph, target, &self.path_end) }
+                { self.render_graph(graph) }
+            </>
+        }
+    }
+
+    fn highlighted_color(&self) -> Option<&str> {
+        if self.highlight {
+            Some(&self.target)
+        } else {
+            None
+        }
+    }
+
+    fn render_graph(&self, graph: &BagGraph) -> Html {
+        const NODE_WIDTH: usize = 160;
+        const LAYER_HEIGHT: usize = 90;
+        const MARGIN: usize = 20;
+
+        let layers = graph.layers();
+        let mut positions = HashMap::with_capacity(graph.contents.len());
+        for (depth, layer) in layers.iter().enumerate() {
+            for (i, &color) in layer.iter().enumerate() {
+                let x = i * NODE_WIDTH + NODE_WIDTH / 2;
+                let y = depth * LAYER_HEIGHT + MARGIN;
+                positions.insert(color, (x, y));
+            }
+        }
+        let width = layers.iter().map(Vec::len).max().unwrap_or(0) * NODE_WIDTH;
+        let height = layers.len().saturating_sub(1) * LAYER_HEIGHT + 2 * MARGIN;
+
+        let highlighted = self
+            .highlighted_color()
+            .map(|color| reachable_from(&graph.contents, color))
+            .unwrap_or_default();
+        let class_for = |color: &str| {
+            if highlighted.contains(color) {
+                "highlighted"
+            } else {
+                ""
+            }
+        };
+
+        let mut edges = graph
+            .contents
+            .iter()
+            .flat_map(|(color, contents)| {
                 contents
                     .iter()
-                    .map(|content| {
-                        content.count * (1 + count_bags_recursive(bags, totals, &content.color))
-                    })
-                    .sum()
-            });
+                    .map(move |content| (color.as_str(), content))
+            })
+            .collect::<Vec<_>>();
+        edges.sort_by(|(a, a_content), (b, b_content)| {
+            (a, &a_content.color).cmp(&(b, &b_content.color))
+        });
+        let render_edge = |(color, content): (&str, &BagContents)| {
+            let (x1, y1) = positions[color];
+            let (x2, y2) = positions[content.color.as_str()];
+            let (y1, y2) = (y1 + 5, y2 - 15);
+            html! {
+                <g class=class_for(color)>
+                    <line x1=x1 y1=y1 x2=x2 y2=y2 />
+                    <text x=(x1 + x2) / 2 y=(y1 + y2) / 2>{ content.count }</text>
+                </g>
+            }
+        };
+        let render_node = |color: &str| {
+            let (x, y) = positions[color];
+            html! {
+                <text class=("bag", class_for(color)) x=x y=y>{ color }</text>
+            }
+        };
 
-            totals.insert(color, total);
-            total
-        })
+        html! {
+            <>
+                <div>
+                    <label>
+                        <input type="checkbox" checked=self.highlight onclick=self.link.callback(|_| Msg::ToggleHighlight) />
+                        <span>{ format!("highlight bags inside {}", self.target) }</span>
+                    </label>
+                    <button onclick=self.link.callback(|_| Msg::DownloadDot)>{ "download DOT" }</button>
+                    <CopyButton text=to_dot(&graph.contents, self.highlighted_color()) />
+                </div>
+                <div class="bag-graph">
+                    <svg width=width height=height viewBox=format!("0 0 {} {}", width, height)>
+                        { for edges.into_iter().map(render_edge) }
+                        { for graph.colors().into_iter().map(render_node) }
+                    </svg>
+                </div>
+            </>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            graph: None,
+            target: COLOR_SHINY_GOLD.to_owned(),
+            path_end: COLOR_SHINY_GOLD.to_owned(),
+            highlight: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => {
+                let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+                let raw_input = input_area.value();
+                match BagGraph::from_input(&raw_input) {
+                    Ok(graph) => {
+                        input_area.set_custom_validity("");
+                        results::record(7, &raw_input);
+                        self.graph = Some(graph);
+                        self.reset_selection();
+                    }
+                    Err(err) => {
+                        input_area.set_custom_validity(&err.to_string());
+                        self.graph = None;
+                    }
+                }
+
+                true
+            }
+            Msg::SetTarget(data) => match selected_value(data) {
+                Some(color) => {
+                    self.target = color;
+                    true
+                }
+                None => false,
+            },
+            Msg::SetPathEnd(data) => match selected_value(data) {
+                Some(color) => {
+                    self.path_end = color;
+                    true
+                }
+                None => false,
+            },
+            Msg::ToggleHighlight => {
+                self.highlight = !self.highlight;
+                true
+            }
+            Msg::DownloadDot => {
+                if let Some(graph) = &self.graph {
+                    let dot = to_dot(&graph.contents, self.highlighted_color());
+                    download::download_text("bags.dot", "text/vnd.graphviz", &dot)
+                        .ok_or_log("failed to download graph");
+                }
+                false
+            }
+        }
     }
 
-    count_bags_recursive(
-        bags,
-        &mut HashMap::with_capacity(bags.len()),
-        COLOR_SHINY_GOLD,
-    )
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link, input_area, ..
+        } = &self;
+        html! {
+            <>
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=7 input=input_area.clone() />
+                <LoadExample day=7 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_queries() }
+            </>
+        }
+    }
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&BagGraph::from_input(input).ok()?).to_string())
 }
 
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(&BagGraph::from_input(input).ok()?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    light red bags contain 1 bright white bag, 2 muted yellow bags.
+    dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+    bright white bags contain 1 shiny gold bag.
+    muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+    shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+    dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+    vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+    faded blue bags contain no other bags.
+    dotted black bags contain no other bags.
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("4"), Some("32")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        light red bags contain 1 bright white bag, 2 muted yellow bags.
-        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-        bright white bags contain 1 shiny gold bag.
-        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-        faded blue bags contain no other bags.
-        dotted black bags contain no other bags.
-    "#;
+    fn example_graph() -> BagGraph {
+        BagGraph::from_input(EXAMPLE_INPUT).expect("failed to parse input")
+    }
 
     #[test]
     fn first() {
+        assert_eq!(first_part(&example_graph()), 4);
+    }
+
+    #[test]
+    fn second() {
+        assert_eq!(second_part(&example_graph()), 32);
+    }
+
+    #[test]
+    fn other_colors() {
+        let graph = example_graph();
         assert_eq!(
-            first_part(&parse_input(EXAMPLE_INPUT).expect("failed to parse input")),
-            4
+            graph
+                .containers_of("faded blue")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                "bright white",
+                "dark olive",
+                "dark orange",
+                "light red",
+                "muted yellow",
+                "shiny gold",
+                "vibrant plum",
+            ]
         );
+        assert!(graph.containers_of("light red").is_empty());
+        assert_eq!(graph.count_inside("dark olive"), 7);
+        assert_eq!(graph.count_inside("faded blue"), 0);
+        assert_eq!(graph.count_inside("unknown"), 0);
     }
 
     #[test]
-    fn second() {
+    fn path() {
+        let graph = example_graph();
+        assert_eq!(
+            graph.path("light red", "dotted black"),
+            Some(vec![
+                "light red",
+                "bright white",
+                "shiny gold",
+                "dark olive",
+                "dotted black"
+            ])
+        );
+        assert_eq!(
+            graph.path("faded blue", "faded blue"),
+            Some(vec!["faded blue"])
+        );
+        assert_eq!(graph.path("shiny gold", "light red"), None);
+        assert_eq!(graph.direct_count("muted yellow", "faded blue"), 9);
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert_eq!(
+            BagGraph::from_input("a b bags contain no other bags.\nc d bags hold 1 a b bag.").err(),
+            Some(GraphError::InvalidRule { line: 2 })
+        );
+        assert_eq!(
+            BagGraph::from_input("\n\n  a b bags contain no other bags.\n  c d bags.").err(),
+            Some(GraphError::InvalidRule { line: 4 })
+        );
+        assert_eq!(
+            BagGraph::from_input(
+                "a b bags contain 1 c d bag.\n\
+                 c d bags contain no other bags.\n\
+                 a b bags contain 2 c d bags."
+            )
+            .err(),
+            Some(GraphError::Duplicate {
+                line: 3,
+                color: "a b".to_owned()
+            })
+        );
+        assert_eq!(
+            BagGraph::from_input(
+                "light red bags contain 1 dark blue bag, 2 pale green bags.\n\
+                 bright white bags contain 3 dark blue bags."
+            )
+            .err(),
+            Some(GraphError::Undefined(vec![
+                "dark blue".to_owned(),
+                "pale green".to_owned()
+            ]))
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let graph = BagGraph::from_input(
+            "\n  a b bags contain 2 c d bags.\n\n  c d bags contain no other bags.\n",
+        )
+        .expect("failed to parse input");
+        assert_eq!(graph.colors(), vec!["a b", "c d"]);
+        assert_eq!(graph.count_inside("a b"), 2);
+    }
+
+    #[test]
+    fn cycles() {
+        let err = BagGraph::from_input(
+            "light red bags contain 1 dark blue bag.\n\
+             dark blue bags contain 2 pale green bags, 1 faded blue bag.\n\
+             faded blue bags contain no other bags.\n\
+             pale green bags contain 1 dark blue bag.",
+        )
+        .err();
+        assert_eq!(
+            err,
+            Some(GraphError::Cycle(vec![
+                "dark blue".to_owned(),
+                "pale green".to_owned(),
+                "dark blue".to_owned()
+            ]))
+        );
+        assert_eq!(
+            err.unwrap().to_string(),
+            "bags contain themselves: dark blue → pale green → dark blue"
+        );
+        assert_eq!(
+            BagGraph::from_input("shiny gold bags contain 1 shiny gold bag.").err(),
+            Some(GraphError::Cycle(vec![
+                "shiny gold".to_owned(),
+                "shiny gold".to_owned()
+            ]))
+        );
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let chain = |depth: usize, count: usize| {
+            (0..depth)
+                .map(|i| format!("a{} bags contain {} a{} bags.\n", i, count, i + 1))
+                .chain(std::iter::once(format!(
+                    "a{} bags contain no other bags.",
+                    depth
+                )))
+                .collect::<String>()
+        };
+
+        let rules = chain(10_000, 1);
+        let graph = BagGraph::from_input(&rules).expect("failed to parse input");
+        assert_eq!(graph.count_inside("a0"), 10_000);
+        assert_eq!(graph.layers().len(), 10_001);
+
+        let rules = chain(1, usize::MAX);
+        let graph = BagGraph::from_input(&rules).expect("failed to parse input");
+        assert_eq!(graph.count_inside("a0"), usize::MAX);
+
+        let rules = format!(
+            "a0 bags contain {} a1 bags.\na1 bags contain 2 a2 bags.\na2 bags contain no other bags.",
+            usize::MAX
+        );
+        let err = BagGraph::from_input(&rules).err();
+        assert_eq!(err, Some(GraphError::Overflow("a0".to_owned())));
+        assert_eq!(
+            err.unwrap().to_string(),
+            "a0 bags contain too many bags to count"
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let bags = parse_input(
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+             bright white bags contain no other bags.\n\
+             muted yellow bags contain 3 bright white bags.",
+        )
+        .expect("failed to parse input");
+        assert_eq!(
+            to_dot(&bags, None),
+            r#"digraph bags {
+    "bright white";
+    "light red";
+    "muted yellow";
+    "light red" -> "bright white" [label=1];
+    "light red" -> "muted yellow" [label=2];
+    "muted yellow" -> "bright white" [label=3];
+}
+"#
+        );
+        assert_eq!(
+            to_dot(&bags, Some("muted yellow")),
+            r#"digraph bags {
+    "bright white" [color=red, fontcolor=red];
+    "light red";
+    "muted yellow" [color=red, fontcolor=red];
+    "light red" -> "bright white" [label=1];
+    "light red" -> "muted yellow" [label=2];
+    "muted yellow" -> "bright white" [label=3, color=red, fontcolor=red];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn layers() {
         assert_eq!(
-            second_part(&parse_input(EXAMPLE_INPUT).expect("failed to parse input")),
-            32
+            example_graph().layers(),
+            vec![
+                vec!["dark orange", "light red"],
+                vec!["bright white", "muted yellow"],
+                vec!["shiny gold"],
+                vec!["dark olive", "vibrant plum"],
+                vec!["dotted black", "faded blue"],
+            ]
         );
     }
 }
@@ -1,14 +1,25 @@
-use std::{collections::HashSet, convert::TryInto};
+use super::{Example, Part};
+use crate::{
+    components::{copy_button::CopyButton, load_example::LoadExample, share_button::ShareButton},
+    services::{results, share},
+};
+use std::{
+    collections::{BTreeSet, HashSet},
+    convert::TryInto,
+    fmt,
+};
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
 
 type Argument = i32;
 
-#[derive(Clone, Default, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 struct State {
     pointer: usize,
     accumulator: Argument,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Operation {
     Acc,
     Jmp,
@@ -51,6 +62,14 @@ impl Operation {
         }
     }
 
+    fn name(self) -> &'static str {
+        match self {
+            Self::Acc => "acc",
+            Self::Jmp => "jmp",
+            Self::Nop => "nop",
+        }
+    }
+
     fn flipped(self) -> Option<Self> {
         match self {
             Self::Acc => None,
@@ -78,6 +97,11 @@ impl Instruction {
         self.op.perform(state, self.arg)
     }
 }
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:+}", self.op.name(), self.arg)
+    }
+}
 
 #[derive(Clone)]
 struct Machine {
@@ -111,6 +135,23 @@ impl Machine {
         }
     }
 
+    /// Like [`step`], but returns a record of what the instruction did.
+    fn step_traced(&mut self) -> Option<TraceEntry> {
+        let instr = self.instructions.get(self.state.pointer)?;
+        let before = self.state.clone();
+        if !instr.execute(&mut self.state) {
+            return None;
+        }
+
+        Some(TraceEntry {
+            pointer: before.pointer,
+            op: instr.op,
+            arg: instr.arg,
+            accumulator_before: before.accumulator,
+            accumulator_after: self.state.accumulator,
+        })
+    }
+
     fn step_while(&mut self, mut f: impl FnMut(&State) -> bool) -> bool {
         while f(&self.state) {
             if !self.step() {
@@ -130,6 +171,165 @@ impl Machine {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Status {
+    Paused,
+    /// The next instruction has a breakpoint.
+    Breakpoint,
+    /// The next instruction has already been executed.
+    Loop,
+    Finished,
+    /// The program jumped outside of the instructions.
+    Crashed,
+}
+impl Status {
+    fn halted(self) -> bool {
+        matches!(self, Self::Finished | Self::Crashed)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Paused => "paused",
+            Self::Breakpoint => "hit breakpoint",
+            Self::Loop => "about to enter a loop",
+            Self::Finished => "finished",
+            Self::Crashed => "jumped outside of the program",
+        }
+    }
+}
+
+/// A single executed instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TraceEntry {
+    pointer: usize,
+    op: Operation,
+    arg: Argument,
+    accumulator_before: Argument,
+    accumulator_after: Argument,
+}
+impl TraceEntry {
+    /// State of the machine right before the instruction was executed.
+    fn state_before(&self) -> State {
+        State {
+            pointer: self.pointer,
+            accumulator: self.accumulator_before,
+        }
+    }
+}
+
+/// Runs a [`Machine`] one instruction at a time, recording every executed instruction.
+struct Debugger {
+    machine: Machine,
+    trace: Vec<TraceEntry>,
+    /// Instructions which have been executed at least once.
+    visited: BTreeSet<usize>,
+    status: Status,
+}
+impl Debugger {
+    fn new(machine: Machine) -> Self {
+        let mut debugger = Self {
+            machine,
+            trace: Vec::new(),
+            visited: BTreeSet::new(),
+            status: Status::Paused,
+        };
+        debugger.update_status(true, &BTreeSet::new());
+        debugger
+    }
+
+    fn update_status(&mut self, stepped: bool, breakpoints: &BTreeSet<usize>) {
+        let pointer = self.machine.state.pointer;
+        self.status = if self.machine.finished() {
+            Status::Finished
+        } else if !stepped || pointer > self.machine.instructions.len() {
+            Status::Crashed
+        } else if self.visited.contains(&pointer) {
+            Status::Loop
+        } else if breakpoints.contains(&pointer) {
+            Status::Breakpoint
+        } else {
+            Status::Paused
+        };
+    }
+
+    /// Executes the next instruction unless the program already halted.
+    fn step(&mut self, breakpoints: &BTreeSet<usize>) {
+        if self.status.halted() {
+            return;
+        }
+
+        let stepped = match self.machine.step_traced() {
+            Some(entry) => {
+                self.visited.insert(entry.pointer);
+                self.trace.push(entry);
+                true
+            }
+            None => false,
+        };
+        self.update_status(stepped, breakpoints);
+    }
+
+    /// Restores the state from right before the given step was executed and forgets all later steps.
+    fn rewind(&mut self, step: usize, breakpoints: &BTreeSet<usize>) {
+        let state = match self.trace.get(step) {
+            Some(entry) => entry.state_before(),
+            None => return,
+        };
+
+        self.machine.state = state;
+        self.trace.truncate(step);
+        self.visited = self.trace.iter().map(|entry| entry.pointer).collect();
+        self.update_status(true, breakpoints);
+    }
+
+    fn step_back(&mut self, breakpoints: &BTreeSet<usize>) {
+        // a jump before the start doesn't move the pointer and isn't recorded,
+        // so stepping back only has to undo the crash
+        if self.failed_step() {
+            self.update_status(true, breakpoints);
+        } else if let Some(step) = self.trace.len().checked_sub(1) {
+            self.rewind(step, breakpoints);
+        }
+    }
+
+    /// Whether the machine crashed trying to execute the current instruction.
+    fn failed_step(&self) -> bool {
+        self.status == Status::Crashed
+            && self.machine.state.pointer < self.machine.instructions.len()
+    }
+
+    fn can_step_back(&self) -> bool {
+        !self.trace.is_empty() || self.failed_step()
+    }
+
+    /// Step which executed the first instruction of the loop the machine is about to repeat.
+    fn loop_start(&self) -> Option<usize> {
+        if self.status != Status::Loop {
+            return None;
+        }
+        let pointer = self.machine.state.pointer;
+        self.trace.iter().position(|entry| entry.pointer == pointer)
+    }
+
+    /// Step which led into the loop the machine is about to repeat.
+    ///
+    /// This is the step right before [`loop_start`], or the very first one if the program starts
+    /// with the loop.
+    fn loop_entry(&self) -> Option<usize> {
+        self.loop_start().map(|step| step.saturating_sub(1))
+    }
+
+    /// Keeps stepping until the program halts, reaches a breakpoint or is about to loop.
+    ///
+    /// Always executes at least one instruction, so it can be used to continue from a breakpoint.
+    fn run(&mut self, breakpoints: &BTreeSet<usize>) {
+        self.step(breakpoints);
+        while self.status == Status::Paused {
+            self.step(breakpoints);
+        }
+    }
+}
+
 fn first_part(machine: &mut Machine) -> Option<Argument> {
     if machine.run_until_loop() {
         Some(machine.state.accumulator)
@@ -138,42 +338,408 @@ fn first_part(machine: &mut Machine) -> Option<Argument> {
     }
 }
 
-fn second_part(original_machine: &Machine) -> Option<Argument> {
-    let mut machines = Vec::new();
-    for (i, instr) in original_machine.instructions.iter().enumerate() {
-        if let Some(flipped) = instr.op.flipped() {
-            let mut machine = original_machine.clone();
-            // since this is a clone we know for sure that this isn't going to panic
-            machine.instructions[i].op = flipped;
-            machines.push(machine);
+/// Instruction to execute after the one at `pointer`, `None` if the jump leads before the start.
+fn successor(op: Operation, pointer: usize, arg: Argument) -> Option<usize> {
+    let mut state = State {
+        pointer,
+        accumulator: 0,
+    };
+    if op.perform(&mut state, arg) {
+        Some(state.pointer)
+    } else {
+        None
+    }
+}
+
+/// A `jmp` or `nop` instruction which, when flipped, makes the program terminate.
+#[derive(Debug, PartialEq)]
+struct Repair {
+    index: usize,
+    /// Value of the accumulator after the repaired program terminated.
+    accumulator: Argument,
+}
+
+/// Finds the instruction to flip in linear time.
+///
+/// Flipping an instruction only changes where that one instruction leads, so it fixes the program
+/// exactly when the program reaches it and its flipped successor terminates without any changes.
+/// The instructions which terminate are found by walking the reversed edges back from the end.
+fn find_repair(instructions: &[Instruction]) -> Option<Repair> {
+    let end = instructions.len();
+    let mut predecessors = vec![Vec::new(); end + 1];
+    for (i, instr) in instructions.iter().enumerate() {
+        if let Some(next) = successor(instr.op, i, instr.arg).filter(|&next| next <= end) {
+            predecessors[next].push(i);
+        }
+    }
+
+    let mut terminates = vec![false; end + 1];
+    terminates[end] = true;
+    let mut stack = vec![end];
+    while let Some(i) = stack.pop() {
+        for &prev in &predecessors[i] {
+            if !terminates[prev] {
+                terminates[prev] = true;
+                stack.push(prev);
+            }
         }
     }
 
-    for mut machine in machines {
-        if !machine.run_until_loop() && machine.finished() {
-            return Some(machine.state.accumulator);
+    let mut seen = vec![false; end];
+    let mut pointer = 0;
+    let index = loop {
+        let instr = instructions.get(pointer)?;
+        if seen[pointer] {
+            return None;
+        }
+        seen[pointer] = true;
+
+        let fixed = instr
+            .op
+            .flipped()
+            .and_then(|flipped| successor(flipped, pointer, instr.arg))
+            .map_or(false, |next| next <= end && terminates[next]);
+        if fixed {
+            break pointer;
         }
+        pointer = successor(instr.op, pointer, instr.arg)?;
+    };
+
+    let mut machine = Machine::new(instructions.to_vec());
+    machine.instructions[index].op = machine.instructions[index].op.flipped()?;
+    machine.run_until_loop();
+    if machine.finished() {
+        Some(Repair {
+            index,
+            accumulator: machine.state.accumulator,
+        })
+    } else {
+        None
     }
+}
+
+fn second_part(machine: &Machine) -> Option<Argument> {
+    find_repair(&machine.instructions).map(|repair| repair.accumulator)
+}
 
-    None
+pub enum Msg {
+    Solve,
+    Step,
+    Run,
+    StepBack,
+    Rewind(usize),
+    RewindToLoopEntry,
+    Reset,
+    ToggleBreakpoint(usize),
 }
 
+pub struct Page {
+    link: ComponentLink<Self>,
+    input_area: NodeRef,
+    debugger: Option<Debugger>,
+    breakpoints: BTreeSet<usize>,
+    answers: Vec<Option<String>>,
+    repair: Option<Repair>,
+}
+impl Page {
+    fn load(&mut self) {
+        let input_area = self.input_area.cast::<HtmlTextAreaElement>().unwrap();
+        let raw_input = input_area.value();
+        if let Some(machine) = Machine::from_input(&raw_input) {
+            input_area.set_custom_validity("");
+            let len = machine.instructions.len();
+            self.breakpoints = self.breakpoints.range(..len).copied().collect();
+            self.repair = find_repair(&machine.instructions);
+            self.debugger = Some(Debugger::new(machine));
+            self.answers = results::record(8, &raw_input)
+                .map(|result| result.parts.into_iter().map(|part| part.answer).collect())
+                .unwrap_or_default();
+        } else {
+            input_area.set_custom_validity("invalid instruction");
+            self.debugger = None;
+            self.answers.clear();
+            self.repair = None;
+        }
+    }
+
+    fn render_answers(&self) -> Html {
+        let render_answer = |(i, answer): (usize, &Option<String>)| {
+            if let Some(answer) = answer {
+                html! {
+                    <p>
+                        { format!("part {}: ", i + 1) }
+                        <code>{ answer }</code>
+                        <CopyButton text=answer.clone() />
+                    </p>
+                }
+            } else {
+                html! { <p>{ format!("part {}: no answer", i + 1) }</p> }
+            }
+        };
+        html! {
+            { for self.answers.iter().enumerate().map(render_answer) }
+        }
+    }
+
+    fn render_instruction(&self, debugger: &Debugger, (i, instr): (usize, &Instruction)) -> Html {
+        let classes = vec![
+            if i == debugger.machine.state.pointer {
+                "current"
+            } else {
+                ""
+            },
+            if debugger.visited.contains(&i) {
+                "visited"
+            } else {
+                ""
+            },
+            if self.breakpoints.contains(&i) {
+                "breakpoint"
+            } else {
+                ""
+            },
+            if self
+                .repair
+                .as_ref()
+                .map_or(false, |repair| repair.index == i)
+            {
+                "repair"
+            } else {
+                ""
+            },
+        ];
+        html! {
+            <li class=classes onclick=self.link.callback(move |_| Msg::ToggleBreakpoint(i))>
+                <span class="index">{ i }</span>
+                <code>{ instr }</code>
+            </li>
+        }
+    }
+
+    fn render_debugger(&self) -> Html {
+        let debugger = match &self.debugger {
+            Some(debugger) => debugger,
+            None => return html! {},
+        };
+        let Debugger {
+            machine,
+            visited,
+            status,
+            ..
+        } = debugger;
+
+        html! {
+            <>
+                <div>
+                    <button onclick=self.link.callback(|_| Msg::Step) disabled=status.halted()>{ "step" }</button>
+                    <button onclick=self.link.callback(|_| Msg::Run) disabled=status.halted()>{ "run" }</button>
+                    <button onclick=self.link.callback(|_| Msg::StepBack) disabled=!debugger.can_step_back()>{ "step back" }</button>
+                    <button onclick=self.link.callback(|_| Msg::RewindToLoopEntry) disabled=debugger.loop_entry().is_none()>
+                        { "rewind to loop entry" }
+                    </button>
+                    <button onclick=self.link.callback(|_| Msg::Reset)>{ "reset" }</button>
+                </div>
+                <p>
+                    { "pointer: " }
+                    <code>{ machine.state.pointer }</code>
+                    { " accumulator: " }
+                    <code>{ machine.state.accumulator }</code>
+                    { format!(" ({})", status.label()) }
+                </p>
+                <p>
+                    { format!("visited {} of {} instructions: ", visited.len(), machine.instructions.len()) }
+                    { visited.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ") }
+                </p>
+                { self.render_repair(machine) }
+                <p>{ "click an instruction to toggle its breakpoint" }</p>
+                <ol class="listing">
+                    { for machine.instructions.iter().enumerate().map(|instr| self.render_instruction(debugger, instr)) }
+                </ol>
+                { self.render_trace(debugger) }
+            </>
+        }
+    }
+
+    fn render_repair(&self, machine: &Machine) -> Html {
+        match &self.repair {
+            Some(Repair { index, accumulator }) => {
+                let instr = &machine.instructions[*index];
+                html! {
+                    <p>
+                        { format!("flipping instruction {} (", index) }
+                        <code>{ instr }</code>
+                        { ") makes the program terminate with accumulator " }
+                        <code>{ accumulator }</code>
+                    </p>
+                }
+            }
+            None => {
+                html! { <p>{ "no single flipped instruction makes the program terminate" }</p> }
+            }
+        }
+    }
+
+    fn render_trace(&self, debugger: &Debugger) -> Html {
+        if debugger.trace.is_empty() {
+            return html! {};
+        }
+
+        let loop_start = debugger.loop_start();
+        let render_entry = |(step, entry): (usize, &TraceEntry)| {
+            let class = if loop_start.map_or(false, |start| step >= start) {
+                "loop"
+            } else {
+                ""
+            };
+            html! {
+                <tr class=class onclick=self.link.callback(move |_| Msg::Rewind(step))>
+                    <td>{ step }</td>
+                    <td>{ entry.pointer }</td>
+                    <td><code>{ format!("{} {:+}", entry.op.name(), entry.arg) }</code></td>
+                    <td>{ entry.accumulator_before }</td>
+                    <td>{ entry.accumulator_after }</td>
+                </tr>
+            }
+        };
+        html! {
+            <>
+                <p>{ "click a step to rewind to it" }</p>
+                <table class="trace">
+                    <thead>
+                        <tr>
+                            <th>{ "step" }</th>
+                            <th>{ "pointer" }</th>
+                            <th>{ "instruction" }</th>
+                            <th>{ "acc before" }</th>
+                            <th>{ "acc after" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for debugger.trace.iter().enumerate().map(render_entry) }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+}
+impl Component for Page {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            input_area: NodeRef::default(),
+            debugger: None,
+            breakpoints: BTreeSet::new(),
+            answers: Vec::new(),
+            repair: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Solve => self.load(),
+            Msg::Reset => {
+                if let Some(debugger) = &mut self.debugger {
+                    *debugger = Debugger::new(Machine::new(debugger.machine.instructions.clone()));
+                }
+            }
+            Msg::Step => {
+                if let Some(debugger) = &mut self.debugger {
+                    debugger.step(&self.breakpoints);
+                }
+            }
+            Msg::Run => {
+                if let Some(debugger) = &mut self.debugger {
+                    debugger.run(&self.breakpoints);
+                }
+            }
+            Msg::StepBack => {
+                if let Some(debugger) = &mut self.debugger {
+                    debugger.step_back(&self.breakpoints);
+                }
+            }
+            Msg::Rewind(step) => {
+                if let Some(debugger) = &mut self.debugger {
+                    debugger.rewind(step, &self.breakpoints);
+                }
+            }
+            Msg::RewindToLoopEntry => {
+                if let Some(debugger) = &mut self.debugger {
+                    if let Some(step) = debugger.loop_entry() {
+                        debugger.rewind(step, &self.breakpoints);
+                    }
+                }
+            }
+            Msg::ToggleBreakpoint(i) => {
+                if !self.breakpoints.remove(&i) {
+                    self.breakpoints.insert(i);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            share::restore_input(&self.input_area);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let Self {
+            link, input_area, ..
+        } = &self;
+        html! {
+            <>
+                <textarea ref=input_area.clone() />
+                <button onclick=link.callback(|_| Msg::Solve)>{ "solve" }</button>
+                <ShareButton day=8 input=input_area.clone() />
+                <LoadExample day=8 input=input_area.clone() onload=link.callback(|_| Msg::Solve) />
+                { self.render_answers() }
+                { self.render_debugger() }
+            </>
+        }
+    }
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    first_part(&mut Machine::from_input(input)?).map(|acc| acc.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&Machine::from_input(input)?).map(|acc| acc.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    nop +0
+    acc +1
+    jmp +4
+    acc +3
+    jmp -3
+    acc -99
+    acc +1
+    jmp -4
+    acc +6
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("5"), Some("8")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        nop +0
-        acc +1
-        jmp +4
-        acc +3
-        jmp -3
-        acc -99
-        acc +1
-        jmp -4
-        acc +6
-    "#;
-
     #[test]
     fn first() {
         assert_eq!(
@@ -189,4 +755,177 @@ mod tests {
             Some(8)
         );
     }
+
+    #[test]
+    fn debugger() {
+        let machine = Machine::from_input(EXAMPLE_INPUT).expect("failed to parse input");
+        assert_eq!(machine.instructions[4].to_string(), "jmp -3");
+
+        let breakpoints = vec![3].into_iter().collect();
+        let mut debugger = Debugger::new(machine);
+        debugger.step(&breakpoints);
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 1,
+                accumulator: 0
+            }
+        );
+        assert_eq!(debugger.status, Status::Paused);
+
+        debugger.run(&breakpoints);
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 3,
+                accumulator: 2
+            }
+        );
+        assert_eq!(debugger.status, Status::Breakpoint);
+
+        debugger.run(&breakpoints);
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 1,
+                accumulator: 5
+            }
+        );
+        assert_eq!(debugger.status, Status::Loop);
+        assert_eq!(
+            debugger.visited.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 6, 7]
+        );
+    }
+
+    #[test]
+    fn debugger_halts() {
+        let no_breakpoints = BTreeSet::new();
+        let mut debugger = Debugger::new(Machine::from_input("acc +1\nnop +0").unwrap());
+        debugger.run(&no_breakpoints);
+        assert_eq!(debugger.status, Status::Finished);
+        debugger.step(&no_breakpoints);
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 2,
+                accumulator: 1
+            }
+        );
+
+        let mut debugger = Debugger::new(Machine::from_input("nop +0\njmp -2").unwrap());
+        debugger.run(&no_breakpoints);
+        assert_eq!(debugger.status, Status::Crashed);
+        assert_eq!(debugger.machine.state.pointer, 1);
+        debugger.step_back(&no_breakpoints);
+        assert_eq!(debugger.status, Status::Paused);
+        assert_eq!(debugger.machine.state.pointer, 1);
+        assert_eq!(debugger.trace.len(), 1);
+
+        let mut debugger = Debugger::new(Machine::from_input("jmp +5").unwrap());
+        debugger.run(&no_breakpoints);
+        assert_eq!(debugger.status, Status::Crashed);
+    }
+
+    #[test]
+    fn trace() {
+        let machine = Machine::from_input(EXAMPLE_INPUT).expect("failed to parse input");
+        let mut debugger = Debugger::new(machine);
+        debugger.run(&BTreeSet::new());
+        assert_eq!(debugger.status, Status::Loop);
+        assert_eq!(
+            debugger
+                .trace
+                .iter()
+                .map(|entry| entry.pointer)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 6, 7, 3, 4]
+        );
+        assert_eq!(
+            debugger.trace[1],
+            TraceEntry {
+                pointer: 1,
+                op: Operation::Acc,
+                arg: 1,
+                accumulator_before: 0,
+                accumulator_after: 1,
+            }
+        );
+        assert_eq!(debugger.loop_start(), Some(1));
+        assert_eq!(debugger.loop_entry(), Some(0));
+
+        debugger.step_back(&BTreeSet::new());
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 4,
+                accumulator: 5
+            }
+        );
+        assert_eq!(debugger.status, Status::Paused);
+        assert!(!debugger.visited.contains(&4));
+        assert_eq!(debugger.loop_entry(), None);
+
+        debugger.rewind(3, &BTreeSet::new());
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 6,
+                accumulator: 1
+            }
+        );
+        assert_eq!(
+            debugger.visited.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn rewind_to_loop_entry() {
+        let machine = Machine::from_input("acc +2\nnop +0\nacc +1\njmp -2").unwrap();
+        let mut debugger = Debugger::new(machine);
+        debugger.run(&BTreeSet::new());
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 1,
+                accumulator: 3
+            }
+        );
+
+        let step = debugger.loop_entry().expect("machine should be looping");
+        debugger.rewind(step, &BTreeSet::new());
+        assert_eq!(
+            debugger.machine.state,
+            State {
+                pointer: 0,
+                accumulator: 0
+            }
+        );
+        assert!(debugger.trace.is_empty());
+    }
+
+    #[test]
+    fn repair() {
+        let machine = Machine::from_input(EXAMPLE_INPUT).expect("failed to parse input");
+        assert_eq!(
+            find_repair(&machine.instructions),
+            Some(Repair {
+                index: 7,
+                accumulator: 8
+            })
+        );
+
+        let machine = Machine::from_input("nop +2\njmp +0\nacc +5").unwrap();
+        assert_eq!(
+            find_repair(&machine.instructions),
+            Some(Repair {
+                index: 0,
+                accumulator: 5
+            })
+        );
+
+        let machine = Machine::from_input("jmp +0\njmp -1").unwrap();
+        assert_eq!(find_repair(&machine.instructions), None);
+    }
 }
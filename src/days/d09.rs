@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::collections::{HashSet, VecDeque};
 
 const PREAMBLE_LEN: usize = 25;
@@ -62,33 +63,48 @@ fn parse_input(s: &str) -> Option<Vec<u64>> {
         .ok()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let (_, n) = Solution::find_first_sequence_break(&parse_input(input)?, PREAMBLE_LEN)?;
+    Some(n.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let sol = Solution::solve_with_preamble(&parse_input(input)?, PREAMBLE_LEN)?;
+    Some(sol.second.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    35
+    20
+    15
+    25
+    47
+    40
+    62
+    55
+    65
+    95
+    102
+    117
+    150
+    182
+    127
+    219
+    299
+    277
+    309
+    576
+"#;
+
+// the official example uses a preamble of 5 instead of 25 and can't be checked by the solver
+pub const EXAMPLES: &[Example] = &[];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        35
-        20
-        15
-        25
-        47
-        40
-        62
-        55
-        65
-        95
-        102
-        117
-        150
-        182
-        127
-        219
-        299
-        277
-        309
-        576
-    "#;
-
     #[test]
     fn solve() {
         let solution = Solution::solve_with_preamble(
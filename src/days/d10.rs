@@ -1,3 +1,5 @@
+use super::{Example, Part};
+
 fn parse_input(s: &str) -> Option<Vec<u16>> {
     s.split_whitespace()
         .map(str::parse)
@@ -66,44 +68,59 @@ fn second_part(mut adapters: Vec<u16>) -> u64 {
     sequences.iter().copied().map(tribonacci).product()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(parse_input(input)?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    28
+    33
+    18
+    42
+    31
+    14
+    46
+    20
+    48
+    47
+    24
+    23
+    49
+    45
+    19
+    38
+    39
+    11
+    1
+    32
+    25
+    35
+    8
+    17
+    7
+    9
+    4
+    2
+    34
+    10
+    3
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("220"), Some("19208")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        28
-        33
-        18
-        42
-        31
-        14
-        46
-        20
-        48
-        47
-        24
-        23
-        49
-        45
-        19
-        38
-        39
-        11
-        1
-        32
-        25
-        35
-        8
-        17
-        7
-        9
-        4
-        2
-        34
-        10
-        3
-    "#;
-
     #[test]
     fn first() {
         let sol = first_part(parse_input(EXAMPLE_INPUT).expect("failed to parse input"));
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::fmt::{self, Display, Formatter};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -143,7 +144,7 @@ impl Layout {
             (1, 1),
         ]
         .iter()
-        .flat_map(move |(dir_row, dir_col)| self.raycast(row, col, *dir_row, *dir_col))
+        .filter_map(move |(dir_row, dir_col)| self.raycast(row, col, *dir_row, *dir_col))
     }
 
     fn adjacents<'a>(
@@ -220,23 +221,38 @@ fn second_part(mut layout: Layout) -> usize {
     }
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(Layout::parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(Layout::parse_input(input)?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    L.LL.LL.LL
+    LLLLLLL.LL
+    L.L.L..L..
+    LLLL.LL.LL
+    L.LL.LL.LL
+    L.LLLLL.LL
+    ..L.L.....
+    LLLLLLLLLL
+    L.LLLLLL.L
+    L.LLLLL.LL
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("37"), Some("26")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        L.LL.LL.LL
-        LLLLLLL.LL
-        L.L.L..L..
-        LLLL.LL.LL
-        L.LL.LL.LL
-        L.LLLLL.LL
-        ..L.L.....
-        LLLLLLLLLL
-        L.LLLLLL.L
-        L.LLLLL.LL
-    "#;
-
     #[test]
     fn first_first_step() {
         let mut layout = Layout::parse_input(EXAMPLE_INPUT).expect("failed to parse input");
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::ops::{Add, AddAssign, Mul, Sub};
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
@@ -43,7 +44,7 @@ impl Add for Position {
 }
 impl AddAssign for Position {
     fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs
+        *self = *self + rhs;
     }
 }
 
@@ -139,10 +140,10 @@ impl Ship {
                 self.waypoint_offset += facing.dir_pos() * amount;
             }
             Instruction::Left(n) => {
-                self.waypoint_offset = self.waypoint_offset.rotate_clockwise(-n)
+                self.waypoint_offset = self.waypoint_offset.rotate_clockwise(-n);
             }
             Instruction::Right(n) => {
-                self.waypoint_offset = self.waypoint_offset.rotate_clockwise(n)
+                self.waypoint_offset = self.waypoint_offset.rotate_clockwise(n);
             }
             Instruction::Forward(steps) => {
                 self.pos += self.waypoint_offset * steps;
@@ -206,18 +207,33 @@ fn second_part(instructions: &[Instruction]) -> usize {
     ship.pos.manhatten_length()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(&parse_input(input)?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    F10
+    N3
+    F7
+    R90
+    F11
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("25"), Some("286")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        F10
-        N3
-        F7
-        R90
-        F11
-    "#;
-
     #[test]
     fn first() {
         let sol = first_part(&parse_input(EXAMPLE_INPUT).expect("failed to parse input"));
@@ -1,3 +1,5 @@
+use super::{Example, Part};
+
 #[derive(Clone, Copy, Debug)]
 enum BusTime {
     None,
@@ -25,7 +27,7 @@ impl BusTime {
 }
 
 fn ceiling_division(a: usize, b: usize) -> usize {
-    a / b + (a % b != 0) as usize
+    a / b + usize::from(a % b != 0)
 }
 
 struct Timetable(Vec<BusTime>);
@@ -84,15 +86,32 @@ fn parse_input(s: &str) -> Option<(Timetable, usize)> {
     Some((timetable, start_time))
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let (table, time) = parse_input(input)?;
+    table.first(time).map(|v| v.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let (table, _) = parse_input(input)?;
+    Some(table.second().to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    939
+    7,13,x,x,59,x,31,19
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("295"), Some("1068781")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        939
-        7,13,x,x,59,x,31,19
-    "#;
-
     #[test]
     fn ceildiv_works() {
         assert_eq!(ceiling_division(5, 3), 2);
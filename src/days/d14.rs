@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::collections::HashMap;
 
 type Memory = HashMap<u64, u64>;
@@ -141,6 +142,41 @@ fn second_part(instrs: impl IntoIterator<Item = Instruction>) -> u64 {
     mem.values().sum()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(parse_input(input)?).to_string())
+}
+
+const FIRST_EXAMPLE_INPUT: &str = r#"
+    mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+    mem[8] = 11
+    mem[7] = 101
+    mem[8] = 0
+"#;
+
+const SECOND_EXAMPLE_INPUT: &str = r#"
+    mask = 000000000000000000000000000000X1001X
+    mem[42] = 100
+    mask = 00000000000000000000000000000000X0XX
+    mem[26] = 1
+"#;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: FIRST_EXAMPLE_INPUT,
+        answers: &[Some("165"), None],
+    },
+    Example {
+        input: SECOND_EXAMPLE_INPUT,
+        answers: &[None, Some("208")],
+    },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,26 +223,13 @@ mod tests {
 
     #[test]
     fn first() {
-        const INPUT: &str = r#"
-            mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
-            mem[8] = 11
-            mem[7] = 101
-            mem[8] = 0
-        "#;
-
-        let instrs = parse_input(INPUT).expect("failed to parse input");
+        let instrs = parse_input(FIRST_EXAMPLE_INPUT).expect("failed to parse input");
         assert_eq!(first_part(instrs), 165);
     }
 
     #[test]
     fn second() {
-        const INPUT: &str = r#"
-            mask = 000000000000000000000000000000X1001X
-            mem[42] = 100
-            mask = 00000000000000000000000000000000X0XX
-            mem[26] = 1
-        "#;
-        let instrs = parse_input(INPUT).expect("failed to parse input");
+        let instrs = parse_input(SECOND_EXAMPLE_INPUT).expect("failed to parse input");
         assert_eq!(second_part(instrs), 208);
     }
 }
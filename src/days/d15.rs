@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::collections::{hash_map::Entry, HashMap};
 
 fn parse_input(s: &str) -> Option<Vec<u32>> {
@@ -37,12 +38,27 @@ fn second_part(starting_nums: impl IntoIterator<Item = u32>) -> u32 {
     run_n_rounds_with(starting_nums, 30_000_000)
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(parse_input(input)?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = "0,3,6";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("436"), Some("175594")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = "0,3,6";
-
     #[test]
     fn first() {
         let nums = parse_input(EXAMPLE_INPUT).expect("failed to parse input");
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::{
     collections::{HashMap, HashSet},
     ops::RangeInclusive,
@@ -14,7 +15,7 @@ fn parse_range(s: &str) -> Option<RangeInclusive<u16>> {
 struct Rule(RangeInclusive<u16>, RangeInclusive<u16>);
 impl Rule {
     fn parse_input(s: &str) -> Option<Self> {
-        let mut it = s.split("or").flat_map(|s| parse_range(s.trim()));
+        let mut it = s.split("or").filter_map(|s| parse_range(s.trim()));
         let first = it.next()?;
         let second = it.next()?;
         Some(Self(first, second))
@@ -170,48 +171,63 @@ fn second_part(input: &Input) -> Option<u64> {
         .product()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&Input::from_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&Input::from_input(input)?).map(|v| v.to_string())
+}
+
+const FIRST_EXAMPLE_INPUT: &str = r#"
+    class: 1-3 or 5-7
+    row: 6-11 or 33-44
+    seat: 13-40 or 45-50
+    
+    your ticket:
+    7,1,14
+    
+    nearby tickets:
+    7,3,47
+    40,4,50
+    55,2,20
+    38,6,12
+"#;
+
+const SECOND_EXAMPLE_INPUT: &str = r#"
+    class: 0-1 or 4-19
+    row: 0-5 or 8-19
+    seat: 0-13 or 16-19
+    
+    your ticket:
+    11,12,13
+    
+    nearby tickets:
+    3,9,18
+    15,1,5
+    5,14,9
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: FIRST_EXAMPLE_INPUT,
+    answers: &[Some("71"), None],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn first() {
-        const EXAMPLE_INPUT: &str = r#"
-            class: 1-3 or 5-7
-            row: 6-11 or 33-44
-            seat: 13-40 or 45-50
-            
-            your ticket:
-            7,1,14
-            
-            nearby tickets:
-            7,3,47
-            40,4,50
-            55,2,20
-            38,6,12
-        "#;
-
-        let input = Input::from_input(EXAMPLE_INPUT).expect("failed to parse input");
+        let input = Input::from_input(FIRST_EXAMPLE_INPUT).expect("failed to parse input");
         assert_eq!(first_part(&input), 71);
     }
 
     #[test]
     fn second() {
-        const EXAMPLE_INPUT: &str = r#"
-            class: 0-1 or 4-19
-            row: 0-5 or 8-19
-            seat: 0-13 or 16-19
-            
-            your ticket:
-            11,12,13
-            
-            nearby tickets:
-            3,9,18
-            15,1,5
-            5,14,9
-        "#;
-
-        let input = Input::from_input(EXAMPLE_INPUT).expect("failed to parse input");
+        let input = Input::from_input(SECOND_EXAMPLE_INPUT).expect("failed to parse input");
         // no example input given here, let's just make sure that it completes
         assert_eq!(second_part(&input), Some(1));
     }
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::{collections::HashSet, convert::TryInto};
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -98,7 +99,7 @@ fn cycle(active_cubes: &HashSet<Coord>, w_enabled: bool) -> HashSet<Coord> {
         let is_active = active_cubes.contains(&coord);
         let neighbor_count = coord
             .iter_neighbors(w_enabled)
-            .filter(|coord| active_cubes.contains(&coord))
+            .filter(|coord| active_cubes.contains(coord))
             .count();
 
         if is_active && !matches!(neighbor_count, 2 | 3) {
@@ -119,16 +120,33 @@ fn perform_some_cycles(mut active_cubes: HashSet<Coord>, w_enabled: bool) -> usi
     active_cubes.len()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+#[allow(clippy::unnecessary_wraps)]
+fn solve_first(input: &str) -> Option<String> {
+    Some(perform_some_cycles(parse_input(input), false).to_string())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn solve_second(input: &str) -> Option<String> {
+    Some(perform_some_cycles(parse_input(input), true).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    .#.
+    ..#
+    ###
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("112"), Some("848")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        .#.
-        ..#
-        ###
-    "#;
-
     #[test]
     fn first() {
         let active_cubes = parse_input(EXAMPLE_INPUT);
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::{
     collections::{HashMap, VecDeque},
     convert::TryInto,
@@ -124,43 +125,57 @@ fn parse_input(s: &str, precedence: &HashMap<Operator, usize>) -> Option<Vec<Exp
 }
 
 fn sum_expressions<'a>(exprs: impl IntoIterator<Item = &'a ExprPostfix>) -> u64 {
-    exprs
+    exprs.into_iter().filter_map(ExprPostfix::calculate).sum()
+}
+
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_with_precedence(input: &str, add: usize, mul: usize) -> Option<String> {
+    let precedence = vec![(Operator::Add, add), (Operator::Mul, mul)]
         .into_iter()
-        .filter_map(|expr| expr.calculate().map(u64::from))
-        .sum()
+        .collect();
+    let exprs = parse_input(input, &precedence)?;
+    Some(sum_expressions(&exprs).to_string())
+}
+
+fn solve_first(input: &str) -> Option<String> {
+    solve_with_precedence(input, 1, 1)
 }
 
+fn solve_second(input: &str) -> Option<String> {
+    solve_with_precedence(input, 2, 1)
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    1 + (2 * 3) + (4 * (5 + 6))
+    2 * 3 + (4 * 5)
+    5 + (8 * 3 + 9 + 3 * 4 * 3)
+    5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+    ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("26386"), Some("693942")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn first() {
-        const INPUT: &str = r#"
-            1 + (2 * 3) + (4 * (5 + 6))
-            2 * 3 + (4 * 5)
-            5 + (8 * 3 + 9 + 3 * 4 * 3)
-            5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
-            ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
-        "#;
         let precedence = vec![(Operator::Add, 1), (Operator::Mul, 1)]
             .into_iter()
             .collect();
-        let exprs = parse_input(INPUT, &precedence).expect("failed to parse input");
+        let exprs = parse_input(EXAMPLE_INPUT, &precedence).expect("failed to parse input");
         assert_eq!(sum_expressions(&exprs), 51 + 26 + 437 + 12_240 + 13_632);
     }
     #[test]
     fn second() {
-        const INPUT: &str = r#"
-            1 + (2 * 3) + (4 * (5 + 6))
-            2 * 3 + (4 * 5)
-            5 + (8 * 3 + 9 + 3 * 4 * 3)
-            5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
-            ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
-        "#;
         let precedence = vec![(Operator::Add, 2), (Operator::Mul, 1)]
             .into_iter()
             .collect();
-        let exprs = parse_input(INPUT, &precedence).expect("failed to parse input");
+        let exprs = parse_input(EXAMPLE_INPUT, &precedence).expect("failed to parse input");
         assert_eq!(sum_expressions(&exprs), 51 + 46 + 1_445 + 669_060 + 23_340);
     }
 }
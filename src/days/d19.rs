@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::collections::HashMap;
 
 struct Matcher<'a> {
@@ -22,7 +23,7 @@ impl<'a> Matcher<'a> {
     }
 
     fn is_complete(&self) -> bool {
-        self.indices.iter().any(|&index| index == self.chars.len())
+        self.indices.contains(&self.chars.len())
     }
 
     fn with_restore_index<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
@@ -63,7 +64,7 @@ impl<'a> Matcher<'a> {
             Rule::Alternatives(alts) => {
                 let new_indices = alts
                     .iter()
-                    .flat_map(|alt| {
+                    .filter_map(|alt| {
                         self.with_restore_index(|matcher| {
                             if matcher.match_rule(alt) {
                                 Some(matcher.indices.clone())
@@ -118,7 +119,7 @@ impl Rule {
     fn parse_input(s: &str) -> Option<Self> {
         let alts = s
             .split('|')
-            .map(|alt| Self::parse_sequence(alt))
+            .map(Self::parse_sequence)
             .collect::<Option<Vec<_>>>()?;
         if alts.len() > 1 {
             Some(Self::Alternatives(alts))
@@ -184,82 +185,106 @@ fn second_part<'a>(rules: &mut RuleSet, messages: impl IntoIterator<Item = &'a s
     first_part(rules, messages)
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let (rules, messages) = parse_input(input)?;
+    Some(first_part(&rules, messages).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let (mut rules, messages) = parse_input(input)?;
+    Some(second_part(&mut rules, messages).to_string())
+}
+
+const FIRST_EXAMPLE_INPUT: &str = r#"
+    0: 4 1 5
+    1: 2 3 | 3 2
+    2: 4 4 | 5 5
+    3: 4 5 | 5 4
+    4: "a"
+    5: "b"
+
+    ababbb
+    bababa
+    abbbab
+    aaabbb
+    aaaabbb
+"#;
+
+const SECOND_EXAMPLE_INPUT: &str = r#"
+    42: 9 14 | 10 1
+    9: 14 27 | 1 26
+    10: 23 14 | 28 1
+    1: "a"
+    11: 42 31
+    5: 1 14 | 15 1
+    19: 14 1 | 14 14
+    12: 24 14 | 19 1
+    16: 15 1 | 14 14
+    31: 14 17 | 1 13
+    6: 14 14 | 1 14
+    2: 1 24 | 14 4
+    0: 8 11
+    13: 14 3 | 1 12
+    15: 1 | 14
+    17: 14 2 | 1 7
+    23: 25 1 | 22 14
+    28: 16 1
+    4: 1 1
+    20: 14 14 | 1 15
+    3: 5 14 | 16 1
+    27: 1 6 | 14 18
+    14: "b"
+    21: 14 1 | 1 14
+    25: 1 1 | 1 14
+    22: 14 14
+    8: 42
+    26: 14 22 | 1 20
+    18: 15 15
+    7: 14 5 | 1 21
+    24: 14 1
+
+    abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+    bbabbbbaabaabba
+    babbbbaabbbbbabbbbbbaabaaabaaa
+    aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+    bbbbbbbaaaabbbbaaabbabaaa
+    bbbababbbbaaaaaaaabbababaaababaabab
+    ababaaaaaabaaab
+    ababaaaaabbbaba
+    baabbaaaabbaaaababbaababb
+    abbbbabbbbaaaababbbbbbaaaababb
+    aaaaabbaabaaaaababaa
+    aaaabbaaaabbaaa
+    aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+    babaaabbbaaabaababbaabababaaab
+    aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
+"#;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: FIRST_EXAMPLE_INPUT,
+        answers: &[Some("2"), None],
+    },
+    Example {
+        input: SECOND_EXAMPLE_INPUT,
+        answers: &[Some("3"), Some("12")],
+    },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn first() {
-        const INPUT: &str = r#"
-            0: 4 1 5
-            1: 2 3 | 3 2
-            2: 4 4 | 5 5
-            3: 4 5 | 5 4
-            4: "a"
-            5: "b"
-
-            ababbb
-            bababa
-            abbbab
-            aaabbb
-            aaaabbb
-        "#;
-
-        let (rules, messages) = parse_input(INPUT).expect("failed to parse input");
+        let (rules, messages) = parse_input(FIRST_EXAMPLE_INPUT).expect("failed to parse input");
         assert_eq!(first_part(&rules, messages), 2);
     }
     #[test]
     fn second() {
-        const INPUT: &str = r#"
-            42: 9 14 | 10 1
-            9: 14 27 | 1 26
-            10: 23 14 | 28 1
-            1: "a"
-            11: 42 31
-            5: 1 14 | 15 1
-            19: 14 1 | 14 14
-            12: 24 14 | 19 1
-            16: 15 1 | 14 14
-            31: 14 17 | 1 13
-            6: 14 14 | 1 14
-            2: 1 24 | 14 4
-            0: 8 11
-            13: 14 3 | 1 12
-            15: 1 | 14
-            17: 14 2 | 1 7
-            23: 25 1 | 22 14
-            28: 16 1
-            4: 1 1
-            20: 14 14 | 1 15
-            3: 5 14 | 16 1
-            27: 1 6 | 14 18
-            14: "b"
-            21: 14 1 | 1 14
-            25: 1 1 | 1 14
-            22: 14 14
-            8: 42
-            26: 14 22 | 1 20
-            18: 15 15
-            7: 14 5 | 1 21
-            24: 14 1
-
-            abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
-            bbabbbbaabaabba
-            babbbbaabbbbbabbbbbbaabaaabaaa
-            aaabbbbbbaaaabaababaabababbabaaabbababababaaa
-            bbbbbbbaaaabbbbaaabbabaaa
-            bbbababbbbaaaaaaaabbababaaababaabab
-            ababaaaaaabaaab
-            ababaaaaabbbaba
-            baabbaaaabbaaaababbaababb
-            abbbbabbbbaaaababbbbbbaaaababb
-            aaaaabbaabaaaaababaa
-            aaaabbaaaabbaaa
-            aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
-            babaaabbbaaabaababbaabababaaab
-            aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
-        "#;
-
-        let (mut rules, messages) = parse_input(INPUT).expect("failed to parse input");
+        let (mut rules, messages) =
+            parse_input(SECOND_EXAMPLE_INPUT).expect("failed to parse input");
         assert_eq!(second_part(&mut rules, messages), 12);
     }
 }
@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     convert::TryInto,
@@ -150,18 +151,12 @@ impl Tile {
         Self::parse_input_from_lines(lines)
     }
 
-    fn iter_rows_tb<'a>(
-        &'a self,
-        x: usize,
-    ) -> impl Iterator<Item = bool> + DoubleEndedIterator + 'a {
-        (0..self.height).map(move |y| self.data.get(&(x, y)).is_some())
+    fn iter_rows_tb<'a>(&'a self, x: usize) -> impl DoubleEndedIterator<Item = bool> + 'a {
+        (0..self.height).map(move |y| self.data.contains(&(x, y)))
     }
 
-    fn iter_cols_lr<'a>(
-        &'a self,
-        y: usize,
-    ) -> impl Iterator<Item = bool> + DoubleEndedIterator + 'a {
-        (0..self.width).map(move |x| self.data.get(&(x, y)).is_some())
+    fn iter_cols_lr<'a>(&'a self, y: usize) -> impl DoubleEndedIterator<Item = bool> + 'a {
+        (0..self.width).map(move |x| self.data.contains(&(x, y)))
     }
 
     fn edges(&self) -> Edges {
@@ -301,12 +296,9 @@ impl AlignedImage {
 
             for mut tile in mem::take(&mut backlog) {
                 let edges = tile.edges();
-                let res = grid
-                    .iter()
-                    .flat_map(|(&(x, y), grid_tile)| {
-                        grid_tile.edges().align(&edges).map(|alig| ((x, y), alig))
-                    })
-                    .next();
+                let res = grid.iter().find_map(|(&(x, y), grid_tile)| {
+                    grid_tile.edges().align(&edges).map(|alig| ((x, y), alig))
+                });
 
                 let ((x, y), alig) = if let Some(v) = res {
                     v
@@ -482,7 +474,7 @@ fn find_corners<'a>(tiles: &'a [Tile]) -> Vec<&'a Tile> {
         }
 
         if dir_count == 2 {
-            corners.push(tile_a)
+            corners.push(tile_a);
         }
     }
 
@@ -510,120 +502,137 @@ fn second_part(image: &mut AlignedImage) -> Option<usize> {
     None
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    let image = AlignedImage::from_tiles(parse_input(input)?)?;
+    first_part(&image).map(|v| v.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    let mut image = AlignedImage::from_tiles(parse_input(input)?)?;
+    second_part(&mut image).map(|v| v.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    Tile 2311:
+    ..##.#..#.
+    ##..#.....
+    #...##..#.
+    ####.#...#
+    ##.##.###.
+    ##...#.###
+    .#.#.#..##
+    ..#....#..
+    ###...#.#.
+    ..###..###
+
+    Tile 1951:
+    #.##...##.
+    #.####...#
+    .....#..##
+    #...######
+    .##.#....#
+    .###.#####
+    ###.##.##.
+    .###....#.
+    ..#.#..#.#
+    #...##.#..
+
+    Tile 1171:
+    ####...##.
+    #..##.#..#
+    ##.#..#.#.
+    .###.####.
+    ..###.####
+    .##....##.
+    .#...####.
+    #.##.####.
+    ####..#...
+    .....##...
+
+    Tile 1427:
+    ###.##.#..
+    .#..#.##..
+    .#.##.#..#
+    #.#.#.##.#
+    ....#...##
+    ...##..##.
+    ...#.#####
+    .#.####.#.
+    ..#..###.#
+    ..##.#..#.
+
+    Tile 1489:
+    ##.#.#....
+    ..##...#..
+    .##..##...
+    ..#...#...
+    #####...#.
+    #..#.#.#.#
+    ...#.#.#..
+    ##.#...##.
+    ..##.##.##
+    ###.##.#..
+
+    Tile 2473:
+    #....####.
+    #..#.##...
+    #.##..#...
+    ######.#.#
+    .#...#.#.#
+    .#########
+    .###.#..#.
+    ########.#
+    ##...##.#.
+    ..###.#.#.
+
+    Tile 2971:
+    ..#.#....#
+    #...###...
+    #.#.###...
+    ##.##..#..
+    .#####..##
+    .#..####.#
+    #..#.#..#.
+    ..####.###
+    ..#.#.###.
+    ...#.#.#.#
+
+    Tile 2729:
+    ...#.#.#.#
+    ####.#....
+    ..#.#.....
+    ....#..#.#
+    .##..##.#.
+    .#.####...
+    ####.#.#..
+    ##.####...
+    ##..#.##..
+    #.##...##.
+
+    Tile 3079:
+    #.#.#####.
+    .#..######
+    ..#.......
+    ######....
+    ####.#..#.
+    .#...#.##.
+    #.#####.##
+    ..#.###...
+    ..#.......
+    ..#.###...
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("20899048083289"), Some("273")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        Tile 2311:
-        ..##.#..#.
-        ##..#.....
-        #...##..#.
-        ####.#...#
-        ##.##.###.
-        ##...#.###
-        .#.#.#..##
-        ..#....#..
-        ###...#.#.
-        ..###..###
-
-        Tile 1951:
-        #.##...##.
-        #.####...#
-        .....#..##
-        #...######
-        .##.#....#
-        .###.#####
-        ###.##.##.
-        .###....#.
-        ..#.#..#.#
-        #...##.#..
-
-        Tile 1171:
-        ####...##.
-        #..##.#..#
-        ##.#..#.#.
-        .###.####.
-        ..###.####
-        .##....##.
-        .#...####.
-        #.##.####.
-        ####..#...
-        .....##...
-
-        Tile 1427:
-        ###.##.#..
-        .#..#.##..
-        .#.##.#..#
-        #.#.#.##.#
-        ....#...##
-        ...##..##.
-        ...#.#####
-        .#.####.#.
-        ..#..###.#
-        ..##.#..#.
-
-        Tile 1489:
-        ##.#.#....
-        ..##...#..
-        .##..##...
-        ..#...#...
-        #####...#.
-        #..#.#.#.#
-        ...#.#.#..
-        ##.#...##.
-        ..##.##.##
-        ###.##.#..
-
-        Tile 2473:
-        #....####.
-        #..#.##...
-        #.##..#...
-        ######.#.#
-        .#...#.#.#
-        .#########
-        .###.#..#.
-        ########.#
-        ##...##.#.
-        ..###.#.#.
-
-        Tile 2971:
-        ..#.#....#
-        #...###...
-        #.#.###...
-        ##.##..#..
-        .#####..##
-        .#..####.#
-        #..#.#..#.
-        ..####.###
-        ..#.#.###.
-        ...#.#.#.#
-
-        Tile 2729:
-        ...#.#.#.#
-        ####.#....
-        ..#.#.....
-        ....#..#.#
-        .##..##.#.
-        .#.####...
-        ####.#.#..
-        ##.####...
-        ##..#.##..
-        #.##...##.
-
-        Tile 3079:
-        #.#.#####.
-        .#..######
-        ..#.......
-        ######....
-        ####.#..#.
-        .#...#.##.
-        #.#####.##
-        ..#.###...
-        ..#.......
-        ..#.###...
-    "#;
-
     #[test]
     fn first() {
         let tiles = parse_input(EXAMPLE_INPUT).expect("failed to parse input");
@@ -638,6 +647,6 @@ mod tests {
     fn second() {
         let tiles = parse_input(EXAMPLE_INPUT).expect("failed to parse input");
         let mut aligned = AlignedImage::from_tiles(tiles).expect("failed to align");
-        assert_eq!(second_part(&mut aligned), Some(273))
+        assert_eq!(second_part(&mut aligned), Some(273));
     }
 }
@@ -1,10 +1,11 @@
+use super::{Example, Part};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 type Food<'a> = (HashSet<&'a str>, HashSet<&'a str>);
 
-fn parse_line(s: &str) -> Option<Food> {
+fn parse_line(s: &str) -> Option<Food<'_>> {
     let mut it = s.split("(contains");
-    let ingredients = it.next()?.trim().split_whitespace().collect();
+    let ingredients = it.next()?.split_whitespace().collect();
     let allergens = it
         .next()?
         .trim()
@@ -15,7 +16,7 @@ fn parse_line(s: &str) -> Option<Food> {
     Some((ingredients, allergens))
 }
 
-fn parse_input(s: &str) -> Option<Vec<Food>> {
+fn parse_input(s: &str) -> Option<Vec<Food<'_>>> {
     s.trim().lines().map(parse_line).collect()
 }
 
@@ -54,9 +55,9 @@ fn determine_ingredients_with_allergens<'a>(
 
         for allergen in allergens {
             let ingredient = allergen2ingredient.remove(allergen)?.into_iter().next()?;
-            allergen2ingredient.values_mut().for_each(|ingredients| {
+            for ingredients in allergen2ingredient.values_mut() {
                 ingredients.remove(ingredient);
-            });
+            }
             ingredient2allergen.insert(ingredient, allergen);
         }
     }
@@ -95,17 +96,32 @@ fn second_part(foods: &[Food]) -> Option<String> {
     Some(ingredients.join(","))
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    first_part(&parse_input(input)?).map(|v| v.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&parse_input(input)?)
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+    trh fvjkl sbzzf mxmxvkd (contains dairy)
+    sqjhc fvjkl (contains soy)
+    sqjhc mxmxvkd sbzzf (contains fish)
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("5"), Some("mxmxvkd,sqjhc,fvjkl")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
-        trh fvjkl sbzzf mxmxvkd (contains dairy)
-        sqjhc fvjkl (contains soy)
-        sqjhc mxmxvkd sbzzf (contains fish)
-    "#;
-
     #[test]
     fn first() {
         let foods = parse_input(EXAMPLE_INPUT).expect("failed to parse input");
@@ -117,6 +133,6 @@ mod tests {
         assert_eq!(
             second_part(&foods).expect("failed to solve"),
             "mxmxvkd,sqjhc,fvjkl"
-        )
+        );
     }
 }
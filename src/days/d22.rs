@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::collections::{HashSet, VecDeque};
 
 type Card = u8;
@@ -114,26 +115,41 @@ fn second_part(players: &mut Players) -> Option<usize> {
     }
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    first_part(&mut parse_players(input)?).map(|v| v.to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&mut parse_players(input)?).map(|v| v.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    Player 1:
+    9
+    2
+    6
+    3
+    1
+    
+    Player 2:
+    5
+    8
+    4
+    7
+    10
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("306"), Some("291")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        Player 1:
-        9
-        2
-        6
-        3
-        1
-        
-        Player 2:
-        5
-        8
-        4
-        7
-        10
-    "#;
-
     #[test]
     fn first() {
         let mut players = parse_players(EXAMPLE_INPUT).expect("failed to parse input");
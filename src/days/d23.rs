@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::string::ToString;
 
 struct FakeLinkedList {
@@ -147,12 +148,27 @@ fn second_part(cups: &mut FakeLinkedList) -> Option<u64> {
     Some(cups.iter_after_label(1).take(2).map(u64::from).product())
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    first_part(&mut FakeLinkedList::from_input(input)?)
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    second_part(&mut FakeLinkedList::from_input(input)?).map(|v| v.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"389125467"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("67384529"), Some("149245887792")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"389125467"#;
-
     #[test]
     fn first() {
         let mut cups = FakeLinkedList::from_input(EXAMPLE_INPUT).expect("failed to parse input");
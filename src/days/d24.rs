@@ -1,3 +1,4 @@
+use super::{Example, Part};
 use std::{collections::HashSet, str::Chars};
 
 // x, y, z
@@ -149,33 +150,48 @@ fn second_part<'a>(targets: impl IntoIterator<Item = &'a Directions>) -> usize {
     tiles.len()
 }
 
+pub const PARTS: &[Part] = &[solve_first, solve_second];
+
+fn solve_first(input: &str) -> Option<String> {
+    Some(first_part(&parse_input(input)?).to_string())
+}
+
+fn solve_second(input: &str) -> Option<String> {
+    Some(second_part(&parse_input(input)?).to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    sesenwnenenewseeswwswswwnenewsewsw
+    neeenesenwnwwswnenewnwwsewnenwseswesw
+    seswneswswsenwwnwse
+    nwnwneseeswswnenewneswwnewseswneseene
+    swweswneswnenwsewnwneneseenw
+    eesenwseswswnenwswnwnwsewwnwsene
+    sewnenenenesenwsewnenwwwse
+    wenwwweseeeweswwwnwwe
+    wsweesenenewnwwnwsenewsenwwsesesenwne
+    neeswseenwwswnwswswnw
+    nenwswwsewswnenenewsenwsenwnesesenew
+    enewnwewneswsewnwswenweswnenwsenwsw
+    sweneswneswneneenwnewenewwneswswnese
+    swwesenesewenwneswnwwneseswwne
+    enesenwswwswneneswsenwnewswseenwsese
+    wnwnesenesenenwwnenwsewesewsesesew
+    nenewswnwewswnenesenwnesewesw
+    eneswnwswnwsenenwnwnwwseeswneewsenese
+    neswnwewnwnwseenwseesewsenwsweewe
+    wseweeenwnesenwwwswnew
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("10"), Some("2208")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = r#"
-        sesenwnenenewseeswwswswwnenewsewsw
-        neeenesenwnwwswnenewnwwsewnenwseswesw
-        seswneswswsenwwnwse
-        nwnwneseeswswnenewneswwnewseswneseene
-        swweswneswnenwsewnwneneseenw
-        eesenwseswswnenwswnwnwsewwnwsene
-        sewnenenenesenwsewnenwwwse
-        wenwwweseeeweswwwnwwe
-        wsweesenenewnwwnwsenewsenwwsesesenwne
-        neeswseenwwswnwswswnw
-        nenwswwsewswnenenewsenwsenwnesesenew
-        enewnwewneswsewnwswenweswnenwsenwsw
-        sweneswneswneneenwnewenewwneswswnese
-        swwesenesewenwneswnwwneseswwne
-        enesenwswwswneneswsenwnewswseenwsese
-        wnwnesenesenenwwnenwsewesewsesesew
-        nenewswnwewswnenesenwnesewesw
-        eneswnwswnwsenenwnwnwwseeswneewsenese
-        neswnwewnwnwseenwseesewsenwsweewe
-        wseweeenwnesenwwwswnew
-    "#;
-
     #[test]
     fn directions_work() {
         let directions = Direction::parse_directions("nwwswee").expect("failed to parse input");
@@ -1,3 +1,5 @@
+use super::{Example, Part};
+
 fn mul_mod_magic(value: u64, subject: u64) -> u64 {
     const MAGIC_NUMBER: u64 = 20_201_227;
     (value * subject) % MAGIC_NUMBER
@@ -40,6 +42,31 @@ fn find_encryption_key(card_pub: u64, door_pub: u64) -> Option<u64> {
     Some(encryption_key)
 }
 
+fn parse_input(s: &str) -> Option<(u64, u64)> {
+    let mut it = s.split_whitespace().map(str::parse);
+    let card_pub = it.next()?.ok()?;
+    let door_pub = it.next()?.ok()?;
+    Some((card_pub, door_pub))
+}
+
+// the second star is awarded for completing all the other puzzles
+pub const PARTS: &[Part] = &[solve_first];
+
+fn solve_first(input: &str) -> Option<String> {
+    let (card_pub, door_pub) = parse_input(input)?;
+    find_encryption_key(card_pub, door_pub).map(|key| key.to_string())
+}
+
+const EXAMPLE_INPUT: &str = r#"
+    5764801
+    17807724
+"#;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: EXAMPLE_INPUT,
+    answers: &[Some("14897079")],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,5 +77,6 @@ mod tests {
             find_encryption_key(5_764_801, 17_807_724).expect("failed to solve"),
             14_897_079
         );
+        assert_eq!(parse_input(EXAMPLE_INPUT), Some((5_764_801, 17_807_724)));
     }
 }
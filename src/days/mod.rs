@@ -26,6 +26,113 @@ mod d25;
 
 pub const AVAILABLE_DAYS: usize = 17;
 
+/// Solves one part of a puzzle for the given input.
+///
+/// Returns `None` if the input couldn't be parsed or solved.
+pub type Part = fn(&str) -> Option<String>;
+
+/// Official example from the puzzle description.
+pub struct Example {
+    /// Raw input as it appears in the source, see [`Example::input`].
+    pub input: &'static str,
+    /// Expected answer for each part, `None` if the example doesn't cover the part.
+    pub answers: &'static [Option<&'static str>],
+}
+impl Example {
+    /// Input without the indentation it has in the source.
+    pub fn input(&self) -> String {
+        self.input
+            .trim()
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Solve the example and compare the answers with the expected ones.
+    ///
+    /// Parts without an expected answer are skipped.
+    pub fn check(&self, parts: &[Part]) -> Vec<PartCheck> {
+        let input = self.input();
+        self.answers
+            .iter()
+            .zip(parts)
+            .enumerate()
+            .filter_map(|(i, (expected, solve))| {
+                let expected = (*expected)?;
+                Some(PartCheck {
+                    part: i + 1,
+                    expected,
+                    actual: solve(&input),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PartCheck {
+    pub part: usize,
+    pub expected: &'static str,
+    pub actual: Option<String>,
+}
+impl PartCheck {
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Some(self.expected)
+    }
+}
+
+/// Everything needed to solve a day without going through its page.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub parts: &'static [Part],
+    pub examples: &'static [Example],
+}
+
+pub fn get_puzzle(day: usize) -> Option<Puzzle> {
+    macro_rules! builder {
+        ($day:ident, $( $num:literal => $module:ident, )+) => {
+            match $day {
+                $(
+                    $num => Some(Puzzle {
+                        parts: $module::PARTS,
+                        examples: $module::EXAMPLES,
+                    }),
+                )*
+                _ => None,
+            }
+        }
+    }
+
+    builder! {day,
+        1 => d01,
+        2 => d02,
+        3 => d03,
+        4 => d04,
+        5 => d05,
+        6 => d06,
+        7 => d07,
+        8 => d08,
+        9 => d09,
+        10 => d10,
+        11 => d11,
+        12 => d12,
+        13 => d13,
+        14 => d14,
+        15 => d15,
+        16 => d16,
+        17 => d17,
+        18 => d18,
+        19 => d19,
+        20 => d20,
+        21 => d21,
+        22 => d22,
+        23 => d23,
+        24 => d24,
+        25 => d25,
+    }
+}
+
 pub fn render_day(day: usize) -> Option<yew::Html> {
     macro_rules! builder {
         ($day:ident, $( $num:literal => $module:path, )+) => {
@@ -40,5 +147,52 @@ pub fn render_day(day: usize) -> Option<yew::Html> {
 
     builder! {day,
         1 => d01,
+        2 => d02,
+        3 => d03,
+        4 => d04,
+        5 => d05,
+        6 => d06,
+        7 => d07,
+        8 => d08,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_example() {
+        let puzzle = get_puzzle(1).expect("missing puzzle");
+        let checks = puzzle.examples[0].check(puzzle.parts);
+        assert_eq!(
+            checks,
+            vec![
+                PartCheck {
+                    part: 1,
+                    expected: "514579",
+                    actual: Some("514579".to_owned()),
+                },
+                PartCheck {
+                    part: 2,
+                    expected: "241861950",
+                    actual: Some("241861950".to_owned()),
+                },
+            ]
+        );
+        assert!(checks.iter().all(PartCheck::passed));
+    }
+
+    #[cfg(feature = "tests-slow")]
+    #[test]
+    fn all_examples() {
+        for day in 1..=25 {
+            let puzzle = get_puzzle(day).expect("missing puzzle");
+            for example in puzzle.examples {
+                for check in example.check(puzzle.parts) {
+                    assert!(check.passed(), "day {}: {:?}", day, check);
+                }
+            }
+        }
     }
 }
@@ -1,6 +1,19 @@
 #![warn(clippy::pedantic)]
 // `needless_lifetimes` currently needed because the rules are different between stable and nightly
 #![allow(dead_code, clippy::needless_lifetimes)]
+// lints added after the toolchain this crate targets, suggesting syntax and std APIs it doesn't have
+// or going against the style of the existing code
+#![allow(
+    unknown_lints,
+    clippy::elidable_lifetime_names,
+    clippy::manual_is_multiple_of,
+    clippy::manual_let_else,
+    clippy::needless_raw_string_hashes,
+    clippy::non_std_lazy_statics,
+    clippy::uninlined_format_args,
+    clippy::unnecessary_map_or
+)]
+#![recursion_limit = "512"]
 
 use yew::prelude::*;
 
@@ -11,6 +24,7 @@ mod router;
 mod services;
 mod utils;
 
+use components::diagnostics::DiagnosticsPanel;
 use router::Router;
 
 struct Model;
@@ -35,11 +49,13 @@ impl Component for Model {
             <>
                 <h1>{ "Advent of Code 2020" }</h1>
                 <Router />
+                <DiagnosticsPanel />
             </>
         }
     }
 }
 
 fn main() {
+    services::panic::install_hook();
     yew::start_app::<Model>();
 }
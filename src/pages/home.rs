@@ -1,4 +1,4 @@
-use crate::components::calendar::Calendar;
+use crate::components::{calendar::Calendar, export_button::ExportButton};
 use yew::prelude::*;
 
 pub struct Home;
@@ -20,7 +20,10 @@ impl Component for Home {
 
     fn view(&self) -> Html {
         html! {
-            <Calendar />
+            <>
+                <Calendar />
+                <ExportButton />
+            </>
         }
     }
 }
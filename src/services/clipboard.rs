@@ -0,0 +1,19 @@
+use js_sys::{Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Write the text to the clipboard.
+///
+/// `web_sys` only exposes the clipboard API behind an unstable flag so it is accessed dynamically.
+/// The returned promise is ignored, the browser takes care of asking for permission.
+pub fn write_text(text: &str) -> Result<(), JsValue> {
+    let navigator = yew::utils::window().navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+    if clipboard.is_undefined() {
+        return Err(JsValue::from_str("clipboard unavailable"));
+    }
+
+    let write_text: Function =
+        Reflect::get(&clipboard, &JsValue::from_str("writeText"))?.dyn_into()?;
+    write_text.call1(&clipboard, &JsValue::from_str(text))?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::{cell::RefCell, rc::Rc};
+use yew::Callback;
+
+/// Maximum number of diagnostics kept around. Older ones are discarded first.
+const MAX_DIAGNOSTICS: usize = 100;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Level {
+    Warning,
+    Error,
+}
+impl Level {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+}
+
+thread_local! {
+    static DIAGNOSTICS: RefCell<Vec<Diagnostic>> = RefCell::default();
+    // we need the second indirection for equality
+    static LISTENERS: RefCell<Vec<Rc<Callback<()>>>> = RefCell::default();
+}
+
+fn notify_listeners() {
+    // listeners are cloned so they're free to (un)subscribe while being notified
+    let listeners = LISTENERS.with(|listeners| listeners.borrow().clone());
+    for listener in listeners {
+        listener.emit(());
+    }
+}
+
+pub struct Subscription(Rc<Callback<()>>);
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let sub = &self.0;
+        LISTENERS.with(|listeners| {
+            let mut listeners = listeners.borrow_mut();
+            if let Some(i) = listeners.iter().position(|other_sub| sub == other_sub) {
+                listeners.remove(i);
+            } else {
+                // reporting this as a diagnostic would be a bit ironic
+                weblog::console_error!("failed to unsubscribe from diagnostics");
+            }
+        });
+    }
+}
+
+/// Subscribe to changes of the collected diagnostics.
+pub fn subscribe(cb: Callback<()>) -> Subscription {
+    let sub = Rc::new(cb);
+    LISTENERS.with(|listeners| listeners.borrow_mut().push(Rc::clone(&sub)));
+    Subscription(sub)
+}
+
+/// Record a diagnostic.
+///
+/// The diagnostic is also sent to the console.
+pub fn report(level: Level, message: impl Into<String>) {
+    let message = message.into();
+    match level {
+        Level::Warning => weblog::console_warn!(message.clone()),
+        Level::Error => weblog::console_error!(message.clone()),
+    }
+
+    DIAGNOSTICS.with(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        if diagnostics.len() >= MAX_DIAGNOSTICS {
+            diagnostics.remove(0);
+        }
+        diagnostics.push(Diagnostic { level, message });
+    });
+    notify_listeners();
+}
+
+pub fn warn(message: impl Into<String>) {
+    report(Level::Warning, message);
+}
+
+pub fn error(message: impl Into<String>) {
+    report(Level::Error, message);
+}
+
+/// Get all collected diagnostics, oldest first.
+pub fn get_all() -> Vec<Diagnostic> {
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().clone())
+}
+
+pub fn clear() {
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().clear());
+    notify_listeners();
+}
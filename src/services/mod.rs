@@ -1 +1,7 @@
+pub mod clipboard;
+pub mod diagnostics;
+pub mod download;
+pub mod panic;
+pub mod results;
 pub mod routing;
+pub mod share;
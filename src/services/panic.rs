@@ -0,0 +1,96 @@
+use std::{any::Any, panic};
+
+const OVERLAY_ID: &str = "panic-overlay";
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+mod sys {
+    use super::OVERLAY_ID;
+    use wasm_bindgen::JsValue;
+    use web_sys::{Document, Element};
+
+    fn create_element(document: &Document, tag: &str, text: &str) -> Result<Element, JsValue> {
+        let element = document.create_element(tag)?;
+        element.set_text_content(Some(text));
+        Ok(element)
+    }
+
+    // The handlers are inline JavaScript because the wasm instance can't be trusted after a panic.
+    fn create_button(document: &Document, text: &str, onclick: &str) -> Result<Element, JsValue> {
+        let button = create_element(document, "button", text)?;
+        button.set_attribute("onclick", onclick)?;
+        Ok(button)
+    }
+
+    /// Show the overlay on top of the current page.
+    ///
+    /// The rest of the document is left untouched so the input remains available.
+    /// Only the first panic is shown because every following one is most likely a consequence.
+    pub fn show_overlay(message: &str, location: Option<&str>) -> Result<(), JsValue> {
+        let document = match web_sys::window().and_then(|window| window.document()) {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        if document.get_element_by_id(OVERLAY_ID).is_some() {
+            return Ok(());
+        }
+        let body = match document.body() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let overlay = document.create_element("aside")?;
+        overlay.set_id(OVERLAY_ID);
+
+        let mut children = vec![
+            create_element(&document, "h2", "Something went terribly wrong")?,
+            create_element(&document, "pre", message)?,
+        ];
+        if let Some(location) = location {
+            children.push(create_element(&document, "code", location)?);
+        }
+        children.push(create_button(
+            &document,
+            "reload",
+            "window.location.reload()",
+        )?);
+        children.push(create_button(
+            &document,
+            "dismiss",
+            "this.parentElement.remove()",
+        )?);
+        for child in &children {
+            overlay.append_child(child)?;
+        }
+
+        body.append_child(&overlay)?;
+        Ok(())
+    }
+}
+
+/// Install a panic hook which surfaces panics in the page.
+///
+/// The message is also logged to the console.
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        let message = payload_message(info.payload());
+        let location = info
+            .location()
+            .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()));
+
+        weblog::console_error!(format!(
+            "panicked at '{}', {}",
+            message,
+            location.as_deref().unwrap_or("unknown location")
+        ));
+        if sys::show_overlay(message, location.as_deref()).is_err() {
+            weblog::console_error!("failed to show panic overlay");
+        }
+    }));
+}
@@ -0,0 +1,229 @@
+//! Records the answers and timings of solved puzzles so they can be exported.
+//!
+//! The export format is described in `DEVELOPERS.md`.
+
+use crate::days;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, collections::BTreeMap, fmt};
+
+/// Version of the export format. Bump this whenever the schema changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PartResult {
+    /// Part number, starting at 1.
+    pub part: usize,
+    /// `None` if the part couldn't be solved.
+    pub answer: Option<String>,
+    pub time_ms: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DayResult {
+    pub day: usize,
+    pub input: String,
+    pub parts: Vec<PartResult>,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid json: {}", err),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version: {}", version)
+            }
+        }
+    }
+}
+
+/// A part whose answer no longer matches the exported one.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub day: usize,
+    pub part: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Export {
+    pub version: u32,
+    pub days: Vec<DayResult>,
+}
+impl Export {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("failed to serialize export")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, ImportError> {
+        let export: Self = serde_json::from_str(s).map_err(ImportError::Json)?;
+        if export.version == FORMAT_VERSION {
+            Ok(export)
+        } else {
+            Err(ImportError::UnsupportedVersion(export.version))
+        }
+    }
+
+    /// Solve all inputs again and compare the answers with the exported ones.
+    pub fn check(&self) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+        for day in &self.days {
+            let parts = days::get_puzzle(day.day).map_or(&[][..], |puzzle| puzzle.parts);
+            for part in &day.parts {
+                let actual = part
+                    .part
+                    .checked_sub(1)
+                    .and_then(|i| parts.get(i))
+                    .and_then(|solve| solve(&day.input));
+                if actual != part.answer {
+                    mismatches.push(Mismatch {
+                        day: day.day,
+                        part: part.part,
+                        expected: part.answer.clone(),
+                        actual,
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+}
+
+thread_local! {
+    static RESULTS: RefCell<BTreeMap<usize, DayResult>> = RefCell::default();
+}
+
+fn now() -> f64 {
+    yew::utils::window()
+        .performance()
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// Solve all parts of the day for the input and record the results.
+///
+/// The parts are solved with the default puzzle parameters, not the settings of the day's page.
+/// Previous results for the same day are replaced.
+pub fn record(day: usize, input: &str) -> Option<DayResult> {
+    let parts = days::get_puzzle(day)?
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(i, solve)| {
+            let start = now();
+            let answer = solve(input);
+            PartResult {
+                part: i + 1,
+                answer,
+                time_ms: now() - start,
+            }
+        })
+        .collect();
+
+    let result = DayResult {
+        day,
+        input: input.to_owned(),
+        parts,
+    };
+    RESULTS.with(|results| results.borrow_mut().insert(day, result.clone()));
+    Some(result)
+}
+
+/// Export all recorded results, ordered by day.
+pub fn export() -> Export {
+    let days = RESULTS.with(|results| results.borrow().values().cloned().collect());
+    Export {
+        version: FORMAT_VERSION,
+        days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::Path};
+
+    fn example_export() -> Export {
+        Export {
+            version: FORMAT_VERSION,
+            days: vec![DayResult {
+                day: 1,
+                input: "1721\n979\n366\n299\n675\n1456\n".to_owned(),
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Some("514579".to_owned()),
+                        time_ms: 0.5,
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: Some("241861950".to_owned()),
+                        time_ms: 1.5,
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let export = example_export();
+        let imported = Export::from_json(&export.to_json()).expect("failed to import");
+        assert_eq!(imported, export);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut export = example_export();
+        export.version = FORMAT_VERSION + 1;
+        assert!(matches!(
+            Export::from_json(&export.to_json()),
+            Err(ImportError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn check_detects_mismatch() {
+        let mut export = example_export();
+        assert_eq!(export.check(), vec![]);
+
+        export.days[0].parts[1].answer = Some("42".to_owned());
+        assert_eq!(
+            export.check(),
+            vec![Mismatch {
+                day: 1,
+                part: 2,
+                expected: Some("42".to_owned()),
+                actual: Some("241861950".to_owned()),
+            }]
+        );
+    }
+
+    /// Every export in the `regressions` directory must still produce the same answers.
+    #[test]
+    fn regressions() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("regressions");
+        let entries = match fs::read_dir(&dir) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        for entry in entries {
+            let path = entry.expect("failed to read directory entry").path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+
+            let export = fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| Export::from_json(&s).ok())
+                .unwrap_or_else(|| panic!("failed to import {}", path.display()));
+            assert_eq!(export.check(), vec![], "{}", path.display());
+        }
+    }
+}
@@ -40,8 +40,13 @@ impl Route {
         sys::with_base_path(|base| format!("{}{}", base, self.into_rel_path()))
     }
 
+    /// Absolute URL including the origin, suitable for sharing.
+    pub fn into_url(self) -> String {
+        format!("{}{}", sys::origin(), self.into_abs_path())
+    }
+
     pub fn into_navigate_callback(self) -> Callback<()> {
-        Callback::from(move |_| navigate_to(self))
+        Callback::from(move |()| navigate_to(self))
     }
 }
 
@@ -53,7 +58,7 @@ mod sys {
 
     pub fn attach_popstate_callback(cb: Callback<PopStateEvent>) -> EventListener {
         EventListener::new(&yew::utils::window(), "popstate", move |e| {
-            cb.emit(e.clone().unchecked_into())
+            cb.emit(e.clone().unchecked_into());
         })
     }
 
@@ -69,6 +74,22 @@ mod sys {
             .ok_or_log("failed to push to history");
     }
 
+    pub fn replace_history(url: &str) {
+        history()
+            .replace_state_with_url(&JsValue::NULL, "", Some(url))
+            .ok_or_log("failed to replace history");
+    }
+
+    pub fn origin() -> String {
+        let location = yew::utils::window().location();
+        location.origin().expect("failed to get origin")
+    }
+
+    pub fn hash() -> String {
+        let location = yew::utils::window().location();
+        location.hash().expect("failed to get hash")
+    }
+
     pub fn abs_path() -> String {
         let location = yew::utils::window().location();
         location.pathname().expect("failed to get pathname")
@@ -95,12 +116,13 @@ mod sys {
             };
         }
 
-        PATHNAME.with(|path| f(&path))
+        PATHNAME.with(|path| f(path))
     }
 }
 
 mod navigate {
     use super::Route;
+    use crate::services::diagnostics;
     use std::{cell::RefCell, rc::Rc};
     use yew::Callback;
 
@@ -131,13 +153,13 @@ mod navigate {
                     })
             });
             if !ok {
-                weblog::console_error!("failed to unsubscribe from navigation");
+                diagnostics::error("failed to unsubscribe from navigation");
             }
         }
     }
 
     pub fn for_each_listener(mut f: impl FnMut(&Callback<Route>)) {
-        LISTENERS.with(|listeners| listeners.borrow().iter().for_each(|sub| f(&**sub)));
+        LISTENERS.with(|listeners| listeners.borrow().iter().for_each(|sub| f(sub)));
     }
 }
 
@@ -160,3 +182,21 @@ pub fn navigate_to(route: Route) {
 pub fn get_current_route() -> Route {
     Route::from_abs_path(&sys::abs_path())
 }
+
+/// Get the fragment of the current URL without the leading '#'.
+pub fn get_current_fragment() -> Option<String> {
+    let hash = sys::hash();
+    let fragment = hash.strip_prefix('#').unwrap_or(&hash);
+    if fragment.is_empty() {
+        None
+    } else {
+        Some(fragment.to_owned())
+    }
+}
+
+/// Replace the current URL without notifying any listeners.
+///
+/// This is meant for changes that don't affect the route, like the fragment.
+pub fn replace_current_url(url: &str) {
+    sys::replace_history(url);
+}
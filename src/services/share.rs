@@ -0,0 +1,107 @@
+use crate::services::{
+    diagnostics,
+    routing::{self, Route},
+};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use std::io::{Read, Write};
+use web_sys::HtmlTextAreaElement;
+use yew::NodeRef;
+
+const FRAGMENT_PREFIX: &str = "input=";
+
+/// Links longer than this are unlikely to survive being pasted around.
+const MAX_URL_LEN: usize = 8 * 1024;
+
+fn encode_input(input: &str) -> String {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    // writing to a `Vec` can't fail
+    encoder
+        .write_all(input.as_bytes())
+        .expect("failed to compress input");
+    let compressed = encoder.finish().expect("failed to compress input");
+    base64::encode_config(compressed, base64::URL_SAFE_NO_PAD)
+}
+
+fn decode_input(encoded: &str) -> Option<String> {
+    let compressed = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).ok()?;
+    let mut input = String::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .read_to_string(&mut input)
+        .ok()?;
+    Some(input)
+}
+
+pub enum Link {
+    /// Link which restores the input when opened.
+    WithInput(String),
+    /// The input was too large, so the link only leads to the route.
+    WithoutInput(String),
+}
+impl Link {
+    pub fn url(&self) -> &str {
+        match self {
+            Self::WithInput(url) | Self::WithoutInput(url) => url,
+        }
+    }
+}
+
+/// Create a link to the route which embeds the input in the fragment.
+pub fn create_link(route: Route, input: &str) -> Link {
+    let url = route.into_url();
+    let url_with_input = format!("{}#{}{}", url, FRAGMENT_PREFIX, encode_input(input));
+    if url_with_input.len() <= MAX_URL_LEN {
+        Link::WithInput(url_with_input)
+    } else {
+        Link::WithoutInput(url)
+    }
+}
+
+/// Get the input embedded in the current URL, if any.
+pub fn get_shared_input() -> Option<String> {
+    let fragment = routing::get_current_fragment()?;
+    let encoded = fragment.strip_prefix(FRAGMENT_PREFIX)?;
+    let input = decode_input(encoded);
+    if input.is_none() {
+        diagnostics::warn("failed to decode the input embedded in the link");
+    }
+
+    input
+}
+
+/// Fill the input area with the input embedded in the current URL.
+///
+/// Returns `true` if there was an input to restore.
+pub fn restore_input(input_area: &NodeRef) -> bool {
+    let input_area = match input_area.cast::<HtmlTextAreaElement>() {
+        Some(v) => v,
+        None => return false,
+    };
+
+    if let Some(input) = get_shared_input() {
+        input_area.set_value(&input);
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+        let encoded = encode_input(input);
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decode_input(&encoded).as_deref(), Some(input));
+    }
+
+    #[test]
+    fn decode_garbage() {
+        assert_eq!(decode_input("not valid!"), None);
+        assert_eq!(decode_input("aGVsbG8"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::services::diagnostics;
+
 pub trait ResultExt<T, E> {
     fn ok_or_log(self, msg: &str) -> Option<T>;
 }
@@ -7,7 +9,7 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
         if let Ok(v) = self {
             Some(v)
         } else {
-            weblog::console_error!(msg);
+            diagnostics::error(msg);
             None
         }
     }